@@ -4,7 +4,11 @@ use std::fs::create_dir_all;
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
 use std_reference::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use std_reference::state::{RefData, ReferenceData};
+use std_reference::state::{
+    AuditEntry, Config, Multisig, PendingAdmin, PendingRound, Proposal, RefData, ReferenceData,
+    RelaySummary, RelayerInfo, RelayerPermissions, Role, SymbolConfig, SymbolEntry, SymbolInfo,
+    TimelockOperation, Vote,
+};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -18,4 +22,19 @@ fn main() {
     export_schema(&schema_for!(QueryMsg), &out_dir);
     export_schema(&schema_for!(RefData), &out_dir);
     export_schema(&schema_for!(ReferenceData), &out_dir);
+    export_schema(&schema_for!(Config), &out_dir);
+    export_schema(&schema_for!(SymbolConfig), &out_dir);
+    export_schema(&schema_for!(PendingRound), &out_dir);
+    export_schema(&schema_for!(PendingAdmin), &out_dir);
+    export_schema(&schema_for!(Role), &out_dir);
+    export_schema(&schema_for!(SymbolInfo), &out_dir);
+    export_schema(&schema_for!(SymbolEntry), &out_dir);
+    export_schema(&schema_for!(RelayerInfo), &out_dir);
+    export_schema(&schema_for!(RelaySummary), &out_dir);
+    export_schema(&schema_for!(RelayerPermissions), &out_dir);
+    export_schema(&schema_for!(TimelockOperation), &out_dir);
+    export_schema(&schema_for!(Multisig), &out_dir);
+    export_schema(&schema_for!(Proposal), &out_dir);
+    export_schema(&schema_for!(Vote), &out_dir);
+    export_schema(&schema_for!(AuditEntry), &out_dir);
 }
@@ -1,21 +1,51 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::slice;
+
 use cosmwasm_std::{
-    entry_point, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError,
-    StdResult, Uint256, Uint64,
+    entry_point, to_binary, to_vec, Addr, Binary, Deps, DepsMut, Env, Event, MessageInfo, Order,
+    Response, StdError, StdResult, Storage, Uint256, Uint64,
 };
 use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::{Bound, Map};
 use semver::Version;
 
 use crate::errors::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::state::{RefData, ReferenceData, ADMIN, REFDATA, RELAYERS};
+use crate::msg::{ConfigUpdate, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, RelayGroup};
+use crate::state::{
+    AuditEntry, Config, Multisig, PendingAdmin, PendingRound, Proposal, RefData, ReferenceData,
+    RelaySummary, RelayerInfo, RelayerPermissions, Role, SkipReason, SymbolConfig, SymbolEntry,
+    SymbolInfo, SymbolRates, TimelockOperation, Vote, ADMIN, ALIASES, AUDIT_LOG, CONFIG, MULTISIG,
+    NEXT_AUDIT_SEQ, NEXT_OPERATION_ID, NEXT_PROPOSAL_ID, PAUSED, PAUSED_SYMBOLS, PENDING_ADMIN,
+    PENDING_ROTATIONS, PENDING_ROUNDS, PROPOSALS, REFDATA, REF_HISTORY, REF_HISTORY_COUNT,
+    RELAYERS, RELAYER_PERMISSIONS, REQUEST_REFS, ROLES, ROUND_SUBMISSIONS, SYMBOLS, SYMBOL_CONFIGS,
+    SYMBOL_IDS, TIMELOCK_OPERATIONS, VOTES,
+};
 
 const E9: Uint64 = Uint64::new(1_000_000_000u64);
 const E18: Uint256 = Uint256::from_u128(1_000_000_000_000_000_000u128);
+const BPS: u128 = 10_000;
+
+// Pagination limits
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
 
 // Version Info
 const CONTRACT_NAME: &str = "band-standard-reference";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// Maximum length of a relayed symbol
+const MAX_SYMBOL_LENGTH: usize = 32;
+
+// Length of a RelayCompact record, a u16 symbol ID followed by a u64 rate
+const COMPACT_RECORD_LEN: usize = 10;
+
+// Maximum timelock delay in seconds, so sensitive operations can never be locked indefinitely
+const MAX_TIMELOCK_DELAY_SECONDS: Uint64 = Uint64::new(30 * 24 * 60 * 60);
+
+// Type and attribute schema version of the per-symbol events emitted by Relay and ForceRelay
+const RELAY_EVENT: &str = "relay_symbol";
+const RELAY_EVENT_SCHEMA_VERSION: &str = "2";
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     mut deps: DepsMut,
@@ -29,38 +59,346 @@ pub fn instantiate(
     // Set sender as admin
     ADMIN.set(deps.branch(), Some(info.sender))?;
 
+    // Set default config
+    CONFIG.save(deps.storage, &Config::default())?;
+
     Ok(Response::default())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    // Sensitive messages can only be executed through the timelock when it is enabled
+    if requires_timelock(deps.storage, &msg)? {
+        return Err(ContractError::TimelockRequired {});
+    }
+
+    dispatch(deps, env, info, msg)
+}
+
+// Routes a message to its handler and records administrative actions in the audit log
+fn dispatch(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    let audit = audit_action(&msg)?;
+    let actor = info.sender.clone();
+    let response = route(deps.branch(), env.clone(), info, msg)?;
+    if let Some((action, params)) = audit {
+        append_audit_entry(deps.storage, &env, actor, action, params)?;
+    }
+
+    Ok(response)
+}
+
+fn route(
+    deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::UpdateAdmin { admin } => {
-            let admin = deps.api.addr_validate(&admin)?;
-            Ok(ADMIN.execute_update_admin(deps, info, Some(admin))?)
+        ExecuteMsg::ProposeAdmin { admin, expires_at } => {
+            execute_propose_admin(deps, info, admin, expires_at)
+        }
+        ExecuteMsg::AcceptAdmin {} => execute_accept_admin(deps, env, info),
+        ExecuteMsg::CancelAdminProposal {} => execute_cancel_admin_proposal(deps, info),
+        ExecuteMsg::Pause { symbols } => execute_set_paused(deps, info, symbols, true),
+        ExecuteMsg::Unpause { symbols } => execute_set_paused(deps, info, symbols, false),
+        ExecuteMsg::GrantRole { role, addresses } => {
+            execute_grant_role(deps, info, role, addresses)
         }
-        ExecuteMsg::AddRelayers { relayers } => execute_add_relayers(deps, info, relayers),
+        ExecuteMsg::RevokeRole { role, addresses } => {
+            execute_revoke_role(deps, info, role, addresses)
+        }
+        ExecuteMsg::AddRelayers { relayers } => execute_add_relayers(deps, env, info, relayers),
         ExecuteMsg::RemoveRelayers { relayers } => execute_remove_relayers(deps, info, relayers),
+        ExecuteMsg::SetRelayerLabel { relayer, label } => {
+            execute_set_relayer_label(deps, info, relayer, label)
+        }
+        ExecuteMsg::SetRelayerPermissions {
+            relayer,
+            permissions,
+        } => execute_set_relayer_permissions(deps, info, relayer, permissions),
+        ExecuteMsg::RotateRelayerKey {
+            new_address,
+            require_confirmation,
+        } => execute_rotate_relayer_key(
+            deps,
+            info,
+            new_address,
+            require_confirmation.unwrap_or(false),
+        ),
+        ExecuteMsg::ConfirmRelayerKey { old_address } => {
+            execute_confirm_relayer_key(deps, info, old_address)
+        }
+        ExecuteMsg::ResignRelayer {} => execute_resign_relayer(deps, info),
         ExecuteMsg::Relay {
             symbol_rates,
             resolve_time,
             request_id,
-        } => execute_relay(deps, info, symbol_rates, resolve_time, request_id),
+            strict,
+        } => {
+            let group = RelayGroup {
+                symbol_rates,
+                resolve_time,
+                request_id,
+            };
+            execute_relay(deps, env, info, vec![group], strict.unwrap_or(false))
+        }
+        ExecuteMsg::RelayBatch { groups, strict } => {
+            execute_relay(deps, env, info, groups, strict.unwrap_or(false))
+        }
+        ExecuteMsg::RelayCompact {
+            data,
+            resolve_time,
+            request_id,
+            strict,
+        } => execute_relay_compact(
+            deps,
+            env,
+            info,
+            data,
+            resolve_time,
+            request_id,
+            strict.unwrap_or(false),
+        ),
         ExecuteMsg::ForceRelay {
             symbol_rates,
             resolve_time,
             request_id,
-        } => execute_force_relay(deps, info, symbol_rates, resolve_time, request_id),
+            strict,
+        } => execute_force_relay(
+            deps,
+            env,
+            info,
+            symbol_rates,
+            resolve_time,
+            request_id,
+            strict.unwrap_or(false),
+        ),
+        ExecuteMsg::UpdateConfig(update) => execute_update_config(deps, info, update),
+        ExecuteMsg::RegisterSymbols { symbols } => execute_register_symbols(deps, info, symbols),
+        ExecuteMsg::DeregisterSymbols { symbols } => {
+            execute_deregister_symbols(deps, info, symbols)
+        }
+        ExecuteMsg::SetAliases { aliases } => execute_set_aliases(deps, info, aliases),
+        ExecuteMsg::RemoveAliases { aliases } => execute_remove_aliases(deps, info, aliases),
+        ExecuteMsg::SetSymbolConfigs { symbol_configs } => {
+            execute_set_symbol_configs(deps, info, symbol_configs)
+        }
+        ExecuteMsg::RemoveSymbolConfigs { symbols } => {
+            execute_remove_symbol_configs(deps, info, symbols)
+        }
+        ExecuteMsg::SetSymbolIds { symbol_ids } => execute_set_symbol_ids(deps, info, symbol_ids),
+        ExecuteMsg::RemoveSymbolIds { ids } => execute_remove_symbol_ids(deps, info, ids),
+        ExecuteMsg::ScheduleOperation { msg } => execute_schedule_operation(deps, env, info, *msg),
+        ExecuteMsg::ExecuteOperation { id } => execute_execute_operation(deps, env, id),
+        ExecuteMsg::CancelOperation { id } => execute_cancel_operation(deps, info, id),
+        ExecuteMsg::ConfigureMultisig { signers, threshold } => {
+            execute_configure_multisig(deps, env, info, signers, threshold)
+        }
+        ExecuteMsg::Propose { msg, expires_at } => {
+            execute_propose(deps, env, info, *msg, expires_at)
+        }
+        ExecuteMsg::Vote { proposal_id, vote } => execute_vote(deps, env, info, proposal_id, vote),
+        ExecuteMsg::ExecuteProposal { proposal_id } => {
+            execute_execute_proposal(deps, env, proposal_id)
+        }
+        ExecuteMsg::RemovePendingRounds { rounds } => {
+            execute_remove_pending_rounds(deps, info, rounds)
+        }
+    }
+}
+
+// Returns the audit log action type and parameters of administrative messages
+fn audit_action(msg: &ExecuteMsg) -> StdResult<Option<(&'static str, Vec<String>)>> {
+    fn json<T: cosmwasm_schema::serde::Serialize>(value: &T) -> StdResult<String> {
+        String::from_utf8(to_vec(value)?).map_err(StdError::from)
+    }
+
+    let audit = match msg {
+        ExecuteMsg::ProposeAdmin { admin, .. } => ("propose_admin", vec![admin.clone()]),
+        ExecuteMsg::AcceptAdmin {} => ("accept_admin", vec![]),
+        ExecuteMsg::CancelAdminProposal {} => ("cancel_admin_proposal", vec![]),
+        ExecuteMsg::Pause { symbols } => ("pause", symbols.clone().unwrap_or_default()),
+        ExecuteMsg::Unpause { symbols } => ("unpause", symbols.clone().unwrap_or_default()),
+        ExecuteMsg::GrantRole { role, addresses } => (
+            "grant_role",
+            [vec![role.to_string()], addresses.clone()].concat(),
+        ),
+        ExecuteMsg::RevokeRole { role, addresses } => (
+            "revoke_role",
+            [vec![role.to_string()], addresses.clone()].concat(),
+        ),
+        ExecuteMsg::AddRelayers { relayers } => ("add_relayers", relayers.clone()),
+        ExecuteMsg::RemoveRelayers { relayers } => ("remove_relayers", relayers.clone()),
+        ExecuteMsg::SetRelayerLabel { relayer, label } => (
+            "set_relayer_label",
+            [vec![relayer.clone()], label.iter().cloned().collect()].concat(),
+        ),
+        ExecuteMsg::SetRelayerPermissions {
+            relayer,
+            permissions,
+        } => (
+            "set_relayer_permissions",
+            vec![relayer.clone(), json(permissions)?],
+        ),
+        ExecuteMsg::RotateRelayerKey { new_address, .. } => {
+            ("rotate_relayer_key", vec![new_address.clone()])
+        }
+        ExecuteMsg::ConfirmRelayerKey { old_address } => {
+            ("confirm_relayer_key", vec![old_address.clone()])
+        }
+        ExecuteMsg::ResignRelayer {} => ("resign_relayer", vec![]),
+        ExecuteMsg::ForceRelay { symbol_rates, .. } => (
+            "force_relay",
+            symbol_rates
+                .iter()
+                .map(|(symbol, _)| symbol.clone())
+                .collect(),
+        ),
+        ExecuteMsg::UpdateConfig(update) => ("update_config", vec![json(update)?]),
+        ExecuteMsg::RegisterSymbols { symbols } => (
+            "register_symbols",
+            symbols.iter().map(|(symbol, _)| symbol.clone()).collect(),
+        ),
+        ExecuteMsg::DeregisterSymbols { symbols } => ("deregister_symbols", symbols.clone()),
+        ExecuteMsg::SetAliases { aliases } => (
+            "set_aliases",
+            aliases
+                .iter()
+                .map(|(alias, symbol)| format!("{}={}", alias, symbol))
+                .collect(),
+        ),
+        ExecuteMsg::RemoveAliases { aliases } => ("remove_aliases", aliases.clone()),
+        ExecuteMsg::SetSymbolConfigs { symbol_configs } => (
+            "set_symbol_configs",
+            symbol_configs
+                .iter()
+                .map(json)
+                .collect::<StdResult<Vec<String>>>()?,
+        ),
+        ExecuteMsg::RemoveSymbolConfigs { symbols } => ("remove_symbol_configs", symbols.clone()),
+        ExecuteMsg::SetSymbolIds { symbol_ids } => (
+            "set_symbol_ids",
+            symbol_ids
+                .iter()
+                .map(|(id, symbol)| format!("{}={}", id, symbol))
+                .collect(),
+        ),
+        ExecuteMsg::RemoveSymbolIds { ids } => (
+            "remove_symbol_ids",
+            ids.iter().map(|id| id.to_string()).collect(),
+        ),
+        ExecuteMsg::ScheduleOperation { msg } => ("schedule_operation", vec![json(msg)?]),
+        ExecuteMsg::ExecuteOperation { id } => ("execute_operation", vec![id.to_string()]),
+        ExecuteMsg::CancelOperation { id } => ("cancel_operation", vec![id.to_string()]),
+        ExecuteMsg::ConfigureMultisig { signers, threshold } => (
+            "configure_multisig",
+            [
+                vec![threshold.to_string()],
+                signers
+                    .iter()
+                    .map(|(signer, weight)| format!("{}={}", signer, weight))
+                    .collect(),
+            ]
+            .concat(),
+        ),
+        ExecuteMsg::Propose { msg, .. } => ("propose", vec![json(msg)?]),
+        ExecuteMsg::Vote { proposal_id, vote } => {
+            ("vote", vec![proposal_id.to_string(), json(vote)?])
+        }
+        ExecuteMsg::ExecuteProposal { proposal_id } => {
+            ("execute_proposal", vec![proposal_id.to_string()])
+        }
+        ExecuteMsg::RemovePendingRounds { rounds } => (
+            "remove_pending_rounds",
+            rounds
+                .iter()
+                .map(|(request_id, resolve_time)| format!("{}:{}", request_id, resolve_time))
+                .collect(),
+        ),
+        ExecuteMsg::Relay { .. }
+        | ExecuteMsg::RelayBatch { .. }
+        | ExecuteMsg::RelayCompact { .. } => return Ok(None),
+    };
+
+    Ok(Some(audit))
+}
+
+fn append_audit_entry(
+    storage: &mut dyn Storage,
+    env: &Env,
+    actor: Addr,
+    action: &str,
+    params: Vec<String>,
+) -> StdResult<()> {
+    let seq = NEXT_AUDIT_SEQ.may_load(storage)?.unwrap_or_default();
+    NEXT_AUDIT_SEQ.save(storage, &(seq + 1))?;
+    AUDIT_LOG.save(
+        storage,
+        seq,
+        &AuditEntry {
+            seq: Uint64::new(seq),
+            height: Uint64::new(env.block.height),
+            time: Uint64::new(env.block.time.seconds()),
+            actor,
+            action: action.to_string(),
+            params,
+        },
+    )
+}
+
+fn is_timelock_msg(msg: &ExecuteMsg) -> bool {
+    matches!(
+        msg,
+        ExecuteMsg::ScheduleOperation { .. }
+            | ExecuteMsg::ExecuteOperation { .. }
+            | ExecuteMsg::CancelOperation { .. }
+    )
+}
+
+fn is_multisig_msg(msg: &ExecuteMsg) -> bool {
+    matches!(
+        msg,
+        ExecuteMsg::Propose { .. } | ExecuteMsg::Vote { .. } | ExecuteMsg::ExecuteProposal { .. }
+    )
+}
+
+// Returns whether a message changes relayers, roles or the admin, or lowers the timelock delay,
+// while the timelock is enabled
+fn requires_timelock(storage: &dyn Storage, msg: &ExecuteMsg) -> StdResult<bool> {
+    match msg {
+        ExecuteMsg::ProposeAdmin { .. }
+        | ExecuteMsg::ConfigureMultisig { .. }
+        | ExecuteMsg::GrantRole { .. }
+        | ExecuteMsg::RevokeRole { .. }
+        | ExecuteMsg::AddRelayers { .. }
+        | ExecuteMsg::RemoveRelayers { .. }
+        | ExecuteMsg::SetRelayerPermissions { .. } => {
+            let config = CONFIG.load(storage)?;
+            Ok(!config.timelock_delay_seconds.is_zero())
+        }
+        ExecuteMsg::UpdateConfig(ConfigUpdate {
+            timelock_delay_seconds: Some(timelock_delay_seconds),
+            ..
+        }) => {
+            let config = CONFIG.load(storage)?;
+            Ok(*timelock_delay_seconds < config.timelock_delay_seconds)
+        }
+        _ => Ok(false),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(mut deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     fn from_semver(err: semver::Error) -> StdError {
         StdError::generic_err(format!("Semver: {}", err))
     }
@@ -88,852 +426,6110 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
         });
     }
 
+    // Set default config if the previous version did not have one
+    if CONFIG.may_load(deps.storage)?.is_none() {
+        CONFIG.save(deps.storage, &Config::default())?;
+    }
+
+    // Relayers were stored as bool before 0.2.0
+    if stored_version < Version::new(0, 2, 0) {
+        migrate_relayers(deps.branch(), &env)?;
+    }
+
+    // Set contract version
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
     Ok(Response::default())
 }
 
-fn execute_add_relayers(
-    deps: DepsMut,
-    info: MessageInfo,
-    relayers: Vec<String>,
-) -> Result<Response, ContractError> {
-    // Checks if sender is admin
-    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+// Converts the relayers stored as bool into RelayerInfo attributed to the current admin
+fn migrate_relayers(deps: DepsMut, env: &Env) -> StdResult<()> {
+    const LEGACY_RELAYERS: Map<&Addr, bool> = Map::new("relayers");
 
-    // Adds relayer
+    let added_by = ADMIN
+        .get(deps.as_ref())?
+        .unwrap_or_else(|| env.contract.address.clone());
+    let relayers = LEGACY_RELAYERS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<Addr>>>()?;
     for relayer in relayers {
-        RELAYERS.save(deps.storage, &deps.api.addr_validate(&relayer)?, &true)?;
+        RELAYERS.save(
+            deps.storage,
+            &relayer,
+            &RelayerInfo::new(Uint64::new(env.block.time.seconds()), added_by.clone()),
+        )?;
     }
 
-    Ok(Response::new().add_attribute("action", "add_relayers"))
+    Ok(())
 }
 
-fn execute_remove_relayers(
+fn execute_propose_admin(
     deps: DepsMut,
     info: MessageInfo,
-    relayers: Vec<String>,
+    admin: String,
+    expires_at: Option<Uint64>,
 ) -> Result<Response, ContractError> {
     // Checks if sender is admin
     ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
 
-    for relayer in relayers {
-        RELAYERS.remove(deps.storage, &deps.api.addr_validate(&relayer)?);
-    }
+    let address = deps.api.addr_validate(&admin)?;
+    PENDING_ADMIN.save(
+        deps.storage,
+        &PendingAdmin {
+            address,
+            expires_at,
+        },
+    )?;
 
-    Ok(Response::new().add_attribute("action", "remove_relayers"))
+    Ok(Response::new()
+        .add_attribute("action", "propose_admin")
+        .add_attribute("pending_admin", admin))
 }
 
-fn execute_relay(
-    deps: DepsMut,
+fn execute_accept_admin(
+    mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    symbol_rates: Vec<(String, Uint64)>,
-    resolve_time: Uint64,
-    request_id: Uint64,
 ) -> Result<Response, ContractError> {
-    // Checks if sender is a relayer
-    let sender_addr = &info.sender;
-    if !query_is_relayer(deps.as_ref(), sender_addr)? {
+    let pending_admin = PENDING_ADMIN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingAdmin {})?;
+
+    // Checks if sender is the proposed admin
+    if info.sender != pending_admin.address {
         return Err(ContractError::Unauthorized {
-            msg: String::from("Sender is not a relayer"),
+            msg: String::from("Sender is not the pending admin"),
         });
     }
 
-    // Saves price data
-    for (symbol, rate) in symbol_rates {
-        if let Some(existing_refdata) = REFDATA.may_load(deps.storage, &symbol)? {
-            if existing_refdata.resolve_time >= resolve_time {
-                continue;
-            }
+    // Checks if the proposal has expired
+    if let Some(expires_at) = pending_admin.expires_at {
+        if env.block.time.seconds() > expires_at.u64() {
+            return Err(ContractError::AdminProposalExpired { expires_at });
         }
-        REFDATA.save(
-            deps.storage,
-            &symbol,
-            &RefData::new(rate, resolve_time, request_id),
-        )?
     }
 
-    Ok(Response::default().add_attribute("action", "execute_relay"))
+    PENDING_ADMIN.remove(deps.storage);
+    ADMIN.set(deps.branch(), Some(info.sender.clone()))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "accept_admin")
+        .add_attribute("admin", info.sender))
 }
 
-fn execute_force_relay(
+fn execute_cancel_admin_proposal(
     deps: DepsMut,
     info: MessageInfo,
-    symbol_rates: Vec<(String, Uint64)>,
-    resolve_time: Uint64,
-    request_id: Uint64,
 ) -> Result<Response, ContractError> {
-    let sender_addr = &info.sender;
+    // Checks if sender is admin
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
 
-    if !query_is_relayer(deps.as_ref(), sender_addr)? {
-        return Err(ContractError::Unauthorized {
-            msg: String::from("Sender is not a relayer"),
-        });
+    if PENDING_ADMIN.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::NoPendingAdmin {});
     }
+    PENDING_ADMIN.remove(deps.storage);
 
-    for (symbol, rate) in symbol_rates {
-        REFDATA.save(
+    Ok(Response::new().add_attribute("action", "cancel_admin_proposal"))
+}
+
+fn execute_grant_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    addresses: Vec<String>,
+) -> Result<Response, ContractError> {
+    // Checks if sender is admin
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    for address in addresses {
+        ROLES.save(
             deps.storage,
-            &symbol,
-            &RefData::new(rate, resolve_time, request_id),
+            (role.as_str(), &deps.api.addr_validate(&address)?),
+            &true,
         )?;
     }
 
-    Ok(Response::default().add_attribute("action", "execute_force_relay"))
+    Ok(Response::new()
+        .add_attribute("action", "grant_role")
+        .add_attribute("role", role.as_str()))
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::Admin {} => to_binary(&ADMIN.query_admin(deps)?),
-        QueryMsg::IsRelayer { relayer } => {
-            to_binary(&query_is_relayer(deps, &deps.api.addr_validate(&relayer)?)?)
-        }
-        QueryMsg::GetRef { symbol } => to_binary(&query_ref(deps, &symbol)?),
-        QueryMsg::GetReferenceData { symbol_pair } => {
-            to_binary(&query_reference_data(deps, &symbol_pair)?)
-        }
-        QueryMsg::GetReferenceDataBulk { symbol_pairs } => {
-            to_binary(&query_reference_data_bulk(deps, &symbol_pairs)?)
+fn execute_revoke_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    addresses: Vec<String>,
+) -> Result<Response, ContractError> {
+    // Checks if sender is admin
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    for address in addresses {
+        ROLES.remove(
+            deps.storage,
+            (role.as_str(), &deps.api.addr_validate(&address)?),
+        );
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_role")
+        .add_attribute("role", role.as_str()))
+}
+
+fn execute_set_paused(
+    deps: DepsMut,
+    info: MessageInfo,
+    symbols: Option<Vec<String>>,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    // Checks if sender is a pauser
+    assert_role(deps.as_ref(), &info.sender, Role::Pauser)?;
+
+    match symbols {
+        Some(symbols) => {
+            for symbol in symbols {
+                if paused {
+                    PAUSED_SYMBOLS.save(deps.storage, &symbol, &true)?;
+                } else {
+                    PAUSED_SYMBOLS.remove(deps.storage, &symbol);
+                }
+            }
         }
+        None => PAUSED.save(deps.storage, &paused)?,
     }
+
+    let action = if paused { "pause" } else { "unpause" };
+    Ok(Response::new().add_attribute("action", action))
 }
 
-fn query_is_relayer(deps: Deps, relayer: &Addr) -> StdResult<bool> {
-    Ok(RELAYERS.may_load(deps.storage, relayer)?.is_some())
+// Checks if relaying and queries of a symbol are paused
+fn is_paused(storage: &dyn Storage, symbol: &str) -> StdResult<bool> {
+    Ok(PAUSED.may_load(storage)?.unwrap_or_default() || PAUSED_SYMBOLS.has(storage, symbol))
 }
 
-fn query_ref(deps: Deps, symbol: &str) -> StdResult<RefData> {
-    if symbol == "USD" {
-        Ok(RefData::new(E9, Uint64::MAX, Uint64::zero()))
+// Checks that the contract is not globally paused
+fn assert_not_paused(storage: &dyn Storage) -> Result<(), ContractError> {
+    if PAUSED.may_load(storage)?.unwrap_or_default() {
+        return Err(ContractError::Paused {});
+    }
+    Ok(())
+}
+
+// Checks if the sender is the admin or has been granted the role
+fn assert_role(deps: Deps, sender: &Addr, role: Role) -> Result<(), ContractError> {
+    if ADMIN.is_admin(deps, sender)? || ROLES.has(deps.storage, (role.as_str(), sender)) {
+        Ok(())
     } else {
-        REFDATA.load(deps.storage, symbol)
+        Err(ContractError::Unauthorized {
+            msg: format!("Sender does not have the {} role", role),
+        })
     }
 }
 
-fn query_reference_data(deps: Deps, symbol_pair: &(String, String)) -> StdResult<ReferenceData> {
-    let base = query_ref(deps, &symbol_pair.0)?;
-    let quote = query_ref(deps, &symbol_pair.1)?;
+fn execute_add_relayers(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    relayers: Vec<String>,
+) -> Result<Response, ContractError> {
+    // Checks if sender is a relayer manager
+    assert_role(deps.as_ref(), &info.sender, Role::RelayerManager)?;
 
-    Ok(ReferenceData::new(
-        Uint256::from(base.rate)
-            .checked_mul(E18)?
-            .checked_div(Uint256::from(quote.rate))?,
-        base.resolve_time,
-        quote.resolve_time,
-    ))
-}
+    // Adds relayer, keeping the metadata of existing relayers
+    for relayer in relayers {
+        let relayer = deps.api.addr_validate(&relayer)?;
+        if !RELAYERS.has(deps.storage, &relayer) {
+            RELAYERS.save(
+                deps.storage,
+                &relayer,
+                &RelayerInfo::new(Uint64::new(env.block.time.seconds()), info.sender.clone()),
+            )?;
+        }
+    }
 
-fn query_reference_data_bulk(
-    deps: Deps,
-    symbol_pairs: &[(String, String)],
-) -> StdResult<Vec<ReferenceData>> {
-    symbol_pairs
-        .iter()
-        .map(|pair| query_reference_data(deps, pair))
-        .collect()
+    Ok(Response::new().add_attribute("action", "add_relayers"))
 }
 
-#[cfg(test)]
-mod tests {
-    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{Addr, Uint256};
+fn execute_remove_relayers(
+    deps: DepsMut,
+    info: MessageInfo,
+    relayers: Vec<String>,
+) -> Result<Response, ContractError> {
+    // Checks if sender is a relayer manager
+    assert_role(deps.as_ref(), &info.sender, Role::RelayerManager)?;
 
-    use crate::msg::ExecuteMsg::{AddRelayers, Relay};
+    for relayer in relayers {
+        remove_relayer(deps.storage, &deps.api.addr_validate(&relayer)?)?;
+    }
 
-    use super::*;
+    Ok(Response::new().add_attribute("action", "remove_relayers"))
+}
 
-    // This function will setup the contract for other tests
-    fn setup(mut deps: DepsMut, sender: &str) {
-        let info = mock_info(sender, &[]);
-        let env = mock_env();
-        instantiate(deps.branch(), env, info, InstantiateMsg {}).unwrap();
+// Removes a relayer together with its permissions, pending key rotation and the submissions it
+// buffered in pending rounds, which no longer count toward the quorum
+fn remove_relayer(storage: &mut dyn Storage, relayer: &Addr) -> StdResult<()> {
+    RELAYERS.remove(storage, relayer);
+    RELAYER_PERMISSIONS.remove(storage, relayer);
+    PENDING_ROTATIONS.remove(storage, relayer);
+    move_round_submissions(storage, relayer, None)
+}
+
+// Moves a relayer's metadata, permissions, force relayer role and pending round submissions to a
+// new address in a single step
+fn move_relayer(storage: &mut dyn Storage, old: &Addr, new: &Addr) -> Result<(), ContractError> {
+    if RELAYERS.has(storage, new) {
+        return Err(ContractError::RelayerExists {
+            relayer: new.clone(),
+        });
     }
 
-    fn is_relayers(deps: Deps, relayers: Vec<Addr>) -> Vec<bool> {
-        relayers
-            .iter()
-            .map(|r| query_is_relayer(deps, r))
-            .collect::<StdResult<Vec<bool>>>()
-            .unwrap()
+    let relayer_info = RELAYERS.load(storage, old)?;
+    let permissions = RELAYER_PERMISSIONS.may_load(storage, old)?;
+    move_round_submissions(storage, old, Some(new))?;
+    remove_relayer(storage, old)?;
+    RELAYERS.save(storage, new, &relayer_info)?;
+    if let Some(permissions) = permissions {
+        RELAYER_PERMISSIONS.save(storage, new, &permissions)?;
     }
+    move_force_relayer_role(storage, old, Some(new))?;
 
-    // This function will setup the relayer for other tests
-    fn setup_relayers(mut deps: DepsMut, sender: &str, relayers: Vec<String>) {
-        setup(deps.branch(), sender);
+    Ok(())
+}
 
-        let info = mock_info(sender, &[]);
-        let env = mock_env();
-        let msg = AddRelayers {
-            relayers: relayers.clone(),
-        };
-        execute(deps.branch(), env, info, msg).unwrap();
+// Moves the force relayer role of a relayer to its new address, or revokes it without one, while
+// every other role is left to GrantRole and RevokeRole
+fn move_force_relayer_role(
+    storage: &mut dyn Storage,
+    old: &Addr,
+    new: Option<&Addr>,
+) -> StdResult<()> {
+    let role = Role::ForceRelayer.as_str();
+    if ROLES.has(storage, (role, old)) {
+        ROLES.remove(storage, (role, old));
+        if let Some(new) = new {
+            ROLES.save(storage, (role, new), &true)?;
+        }
     }
 
-    // This function will setup mock relays for other tests
-    fn setup_relays(
-        mut deps: DepsMut,
-        sender: &str,
-        relayers: Vec<String>,
-        symbol_rates: Vec<(String, Uint64)>,
-        resolve_time: Uint64,
-        request_id: Uint64,
-    ) {
-        setup_relayers(deps.branch(), sender, relayers.clone());
+    Ok(())
+}
 
-        let info = mock_info(relayers[0].as_str(), &[]);
-        let env = mock_env();
+// Moves the pending round submissions of a relayer to its new address, so the new key counts as
+// the same relayer and cannot submit the rounds again, or drops them without a new address
+fn move_round_submissions(
+    storage: &mut dyn Storage,
+    old: &Addr,
+    new: Option<&Addr>,
+) -> StdResult<()> {
+    let rounds = PENDING_ROUNDS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<((u64, u64), PendingRound)>>>()?;
+    for ((request_id, resolve_time), mut round) in rounds {
+        if !round.relayers.contains(old) {
+            continue;
+        }
 
-        let msg = Relay {
-            symbol_rates,
-            resolve_time,
-            request_id,
-        };
-        execute(deps.branch(), env, info, msg).unwrap();
+        // Keeps an earlier submission of the new address instead of counting the relayer twice
+        let submission = ROUND_SUBMISSIONS.load(storage, (request_id, resolve_time, old))?;
+        ROUND_SUBMISSIONS.remove(storage, (request_id, resolve_time, old));
+        match new {
+            Some(new) if !round.relayers.contains(new) => {
+                for relayer in round.relayers.iter_mut().filter(|relayer| *relayer == old) {
+                    *relayer = new.clone();
+                }
+                ROUND_SUBMISSIONS.save(storage, (request_id, resolve_time, new), &submission)?;
+            }
+            _ => round.relayers.retain(|relayer| relayer != old),
+        }
+        if round.relayers.is_empty() {
+            PENDING_ROUNDS.remove(storage, (request_id, resolve_time));
+        } else {
+            PENDING_ROUNDS.save(storage, (request_id, resolve_time), &round)?;
+        }
     }
 
-    mod instantiate {
-        use super::*;
+    Ok(())
+}
+
+fn execute_rotate_relayer_key(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_address: String,
+    require_confirmation: bool,
+) -> Result<Response, ContractError> {
+    // Checks if sender is a relayer
+    if !query_is_relayer(deps.as_ref(), &info.sender)? {
+        return Err(ContractError::Unauthorized {
+            msg: String::from("Sender is not a relayer"),
+        });
+    }
+
+    let new_address = deps.api.addr_validate(&new_address)?;
+    if require_confirmation {
+        // The current key stays active until the new address confirms
+        if RELAYERS.has(deps.storage, &new_address) {
+            return Err(ContractError::RelayerExists {
+                relayer: new_address,
+            });
+        }
+        PENDING_ROTATIONS.save(deps.storage, &info.sender, &new_address)?;
+    } else {
+        move_relayer(deps.storage, &info.sender, &new_address)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "rotate_relayer_key")
+        .add_attribute("new_address", new_address)
+        .add_attribute("confirmed", (!require_confirmation).to_string()))
+}
+
+fn execute_confirm_relayer_key(
+    deps: DepsMut,
+    info: MessageInfo,
+    old_address: String,
+) -> Result<Response, ContractError> {
+    let old_address = deps.api.addr_validate(&old_address)?;
+
+    // Checks if sender is the pending new address of the relayer
+    if PENDING_ROTATIONS.may_load(deps.storage, &old_address)? != Some(info.sender.clone()) {
+        return Err(ContractError::NoPendingRotation {
+            relayer: old_address,
+        });
+    }
+    move_relayer(deps.storage, &old_address, &info.sender)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "confirm_relayer_key")
+        .add_attribute("old_address", old_address))
+}
+
+fn execute_resign_relayer(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    // Checks if sender is a relayer
+    if !query_is_relayer(deps.as_ref(), &info.sender)? {
+        return Err(ContractError::Unauthorized {
+            msg: String::from("Sender is not a relayer"),
+        });
+    }
+
+    remove_relayer(deps.storage, &info.sender)?;
+    move_force_relayer_role(deps.storage, &info.sender, None)?;
+
+    Ok(Response::new().add_attribute("action", "resign_relayer"))
+}
+
+fn execute_set_relayer_permissions(
+    deps: DepsMut,
+    info: MessageInfo,
+    relayer: String,
+    permissions: Option<RelayerPermissions>,
+) -> Result<Response, ContractError> {
+    // Checks if sender is a relayer manager
+    assert_role(deps.as_ref(), &info.sender, Role::RelayerManager)?;
+
+    let relayer = deps.api.addr_validate(&relayer)?;
+    match permissions {
+        Some(permissions) => RELAYER_PERMISSIONS.save(deps.storage, &relayer, &permissions)?,
+        None => RELAYER_PERMISSIONS.remove(deps.storage, &relayer),
+    }
+
+    Ok(Response::new().add_attribute("action", "set_relayer_permissions"))
+}
+
+fn execute_set_relayer_label(
+    deps: DepsMut,
+    info: MessageInfo,
+    relayer: String,
+    label: Option<String>,
+) -> Result<Response, ContractError> {
+    // Checks if sender is a relayer manager
+    assert_role(deps.as_ref(), &info.sender, Role::RelayerManager)?;
+
+    let relayer = deps.api.addr_validate(&relayer)?;
+    let mut relayer_info = RELAYERS.load(deps.storage, &relayer)?;
+    relayer_info.label = label;
+    RELAYERS.save(deps.storage, &relayer, &relayer_info)?;
+
+    Ok(Response::new().add_attribute("action", "set_relayer_label"))
+}
+
+fn execute_relay(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    groups: Vec<RelayGroup>,
+    strict: bool,
+) -> Result<Response, ContractError> {
+    // Checks if sender is a relayer
+    let sender_addr = &info.sender;
+    let mut relayer_info =
+        RELAYERS
+            .may_load(deps.storage, sender_addr)?
+            .ok_or(ContractError::Unauthorized {
+                msg: String::from("Sender is not a relayer"),
+            })?;
+    assert_not_paused(deps.storage)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let count = groups
+        .iter()
+        .map(|group| group.symbol_rates.len())
+        .sum::<usize>() as u32;
+    if config.max_symbols_per_relay > 0 && count > config.max_symbols_per_relay {
+        return Err(ContractError::TooManySymbols {
+            count,
+            max: config.max_symbols_per_relay,
+        });
+    }
+    let mut request_ids = BTreeSet::new();
+    for group in &groups {
+        validate_symbol_rates(deps.storage, &config, &group.symbol_rates)?;
+        assert_symbols_permitted(deps.storage, sender_addr, &group.symbol_rates)?;
+        validate_resolve_time(&env, &config, group.resolve_time)?;
+        // Groups of the same batch are checked against each other as none is applied yet
+        if config.reject_reused_request_ids
+            && (!request_ids.insert(group.request_id.u64())
+                || is_request_applied(deps.storage, group.request_id))
+        {
+            return Err(ContractError::RequestIdAlreadyApplied {
+                request_id: group.request_id,
+            });
+        }
+    }
+
+    // Updates relayer statistics
+    relayer_info.last_relay_time = Some(Uint64::new(env.block.time.seconds()));
+    relayer_info.relay_count += Uint64::one();
+    if let Some(group) = groups.last() {
+        relayer_info.last_request_id = Some(group.request_id);
+    }
+    RELAYERS.save(deps.storage, sender_addr, &relayer_info)?;
+
+    // Applies every group with its own resolve time guard
+    let mut response = Response::default().add_attribute("action", "execute_relay");
+    let mut summary = RelaySummary::default();
+    for group in groups {
+        let (group_response, group_summary) = if config.relay_threshold > 1 {
+            submit_round(
+                deps.branch(),
+                &config,
+                response,
+                sender_addr,
+                group.symbol_rates,
+                group.resolve_time,
+                group.request_id,
+            )?
+        } else {
+            save_symbol_rates(
+                deps.branch(),
+                &config,
+                response,
+                slice::from_ref(sender_addr),
+                group.symbol_rates,
+                group.resolve_time,
+                group.request_id,
+            )?
+        };
+        response = group_response;
+        summary.extend(group_summary);
+    }
+
+    set_relay_summary(response, summary, strict)
+}
+
+fn execute_relay_compact(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    data: Binary,
+    resolve_time: Uint64,
+    request_id: Uint64,
+    strict: bool,
+) -> Result<Response, ContractError> {
+    // Checks if sender is a relayer before resolving the symbol IDs
+    if !RELAYERS.has(deps.storage, &info.sender) {
+        return Err(ContractError::Unauthorized {
+            msg: String::from("Sender is not a relayer"),
+        });
+    }
+
+    let group = RelayGroup {
+        symbol_rates: decode_compact_symbol_rates(deps.storage, &data)?,
+        resolve_time,
+        request_id,
+    };
+    execute_relay(deps, env, info, vec![group], strict)
+}
+
+// Decodes RelayCompact records of a big-endian u16 symbol ID and a big-endian u64 rate
+fn decode_compact_symbol_rates(
+    storage: &dyn Storage,
+    data: &Binary,
+) -> Result<Vec<(String, Uint64)>, ContractError> {
+    if data.len() % COMPACT_RECORD_LEN != 0 {
+        return Err(ContractError::InvalidCompactData {
+            len: data.len() as u32,
+            record_len: COMPACT_RECORD_LEN as u32,
+        });
+    }
+
+    data.chunks_exact(COMPACT_RECORD_LEN)
+        .map(|record| {
+            let id = u16::from_be_bytes([record[0], record[1]]);
+            let mut rate = [0u8; 8];
+            rate.copy_from_slice(&record[2..]);
+            let symbol = SYMBOL_IDS
+                .may_load(storage, id)?
+                .ok_or(ContractError::UnknownSymbolId { id })?;
+            Ok((symbol, Uint64::new(u64::from_be_bytes(rate))))
+        })
+        .collect()
+}
+
+// Sets the RelaySummary as response data, failing in strict mode if any symbol was not saved
+fn set_relay_summary(
+    response: Response,
+    summary: RelaySummary,
+    strict: bool,
+) -> Result<Response, ContractError> {
+    if strict {
+        if let Some((symbol, reason)) = summary.skipped.iter().chain(&summary.rejected).next() {
+            return Err(ContractError::StrictRelayFailed {
+                symbol: symbol.clone(),
+                reason: *reason,
+            });
+        }
+    }
+
+    Ok(response.set_data(to_binary(&summary)?))
+}
+
+// Rejects oversized messages, duplicate or malformed symbols, reserved symbols and zero rates
+fn validate_symbol_rates(
+    storage: &dyn Storage,
+    config: &Config,
+    symbol_rates: &[(String, Uint64)],
+) -> Result<(), ContractError> {
+    let count = symbol_rates.len() as u32;
+    if config.max_symbols_per_relay > 0 && count > config.max_symbols_per_relay {
+        return Err(ContractError::TooManySymbols {
+            count,
+            max: config.max_symbols_per_relay,
+        });
+    }
+
+    let mut seen = BTreeSet::new();
+    for (symbol, rate) in symbol_rates {
+        if symbol.is_empty()
+            || symbol.len() > MAX_SYMBOL_LENGTH
+            || !symbol.chars().all(|c| c.is_ascii_graphic())
+        {
+            return Err(ContractError::InvalidSymbol {
+                symbol: symbol.clone(),
+            });
+        }
+        if !seen.insert(symbol.as_str()) {
+            return Err(ContractError::DuplicateSymbol {
+                symbol: symbol.clone(),
+            });
+        }
+        // The anchor and aliases are resolved before reaching REFDATA so they can never be read
+        if *symbol == config.anchor || ALIASES.has(storage, symbol) {
+            return Err(ContractError::ReservedSymbol {
+                symbol: symbol.clone(),
+            });
+        }
+        if rate.is_zero() {
+            return Err(ContractError::ZeroRate {
+                symbol: symbol.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+// Rejects symbols outside the permissions of a restricted relayer
+fn assert_symbols_permitted(
+    storage: &dyn Storage,
+    relayer: &Addr,
+    symbol_rates: &[(String, Uint64)],
+) -> Result<(), ContractError> {
+    let permissions = match RELAYER_PERMISSIONS.may_load(storage, relayer)? {
+        Some(permissions) => permissions,
+        None => return Ok(()),
+    };
+
+    for (symbol, _) in symbol_rates {
+        if permissions.symbols.contains(symbol) {
+            continue;
+        }
+        let category = SYMBOLS
+            .may_load(storage, symbol)?
+            .map(|symbol_info| symbol_info.category);
+        if !matches!(category, Some(category) if permissions.categories.contains(&category)) {
+            return Err(ContractError::UnauthorizedSymbol {
+                relayer: relayer.clone(),
+                symbol: symbol.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+// Rejects resolve times too far ahead of or behind the block time
+fn validate_resolve_time(
+    env: &Env,
+    config: &Config,
+    resolve_time: Uint64,
+) -> Result<(), ContractError> {
+    let block_time = Uint64::new(env.block.time.seconds());
+    if !config.max_future_drift_seconds.is_zero()
+        && resolve_time > block_time.saturating_add(config.max_future_drift_seconds)
+    {
+        return Err(ContractError::ResolveTimeInFuture {
+            resolve_time,
+            block_time,
+            max_future_drift_seconds: config.max_future_drift_seconds,
+        });
+    }
+    if !config.max_resolve_age_seconds.is_zero()
+        && resolve_time.saturating_add(config.max_resolve_age_seconds) < block_time
+    {
+        return Err(ContractError::ResolveTimeTooOld {
+            resolve_time,
+            block_time,
+            max_resolve_age_seconds: config.max_resolve_age_seconds,
+        });
+    }
+
+    Ok(())
+}
+
+// Buffers a Relay submission and applies the median rate of every symbol once the relay
+// threshold of distinct relayers is reached
+fn submit_round(
+    deps: DepsMut,
+    config: &Config,
+    response: Response,
+    relayer: &Addr,
+    symbol_rates: Vec<(String, Uint64)>,
+    resolve_time: Uint64,
+    request_id: Uint64,
+) -> Result<(Response, RelaySummary), ContractError> {
+    // Skips symbols already saved at this or a newer resolve time, e.g. by the finalized round,
+    // so late submissions never open a round that can no longer reach the threshold
+    let mut response = response;
+    let mut outdated = RelaySummary::default();
+    let mut current = vec![];
+    for (symbol, rate) in symbol_rates {
+        let existing_refdata = REFDATA.may_load(deps.storage, &symbol)?;
+        if !matches!(existing_refdata, Some(refdata) if refdata.resolve_time >= resolve_time) {
+            current.push((symbol, rate));
+            continue;
+        }
+        let event = relay_event(
+            slice::from_ref(relayer),
+            &symbol,
+            resolve_time,
+            request_id,
+            false,
+        )
+        .add_attribute("status", "skipped")
+        .add_attribute("reason", SkipReason::Outdated.as_str())
+        .add_attribute("rate", rate);
+        response = response.add_event(event);
+        outdated.skip(symbol, SkipReason::Outdated);
+    }
+    if current.is_empty() {
+        return Ok((response, outdated));
+    }
+    let symbol_rates = current;
+
+    let round_key = (request_id.u64(), resolve_time.u64());
+    let mut round = PENDING_ROUNDS
+        .may_load(deps.storage, round_key)?
+        .unwrap_or_else(|| PendingRound::new(request_id, resolve_time));
+    if round.relayers.contains(relayer) {
+        return Err(ContractError::AlreadySubmitted {
+            relayer: relayer.clone(),
+            request_id,
+            resolve_time,
+        });
+    }
+    round.relayers.push(relayer.clone());
+    let pending = symbol_rates
+        .iter()
+        .map(|(symbol, _)| symbol.clone())
+        .collect();
+    ROUND_SUBMISSIONS.save(
+        deps.storage,
+        (round_key.0, round_key.1, relayer),
+        &symbol_rates,
+    )?;
+
+    let submissions = round.relayers.len() as u32;
+    let response = response.add_attribute("submissions", submissions.to_string());
+    if submissions < config.relay_threshold {
+        PENDING_ROUNDS.save(deps.storage, round_key, &round)?;
+        let summary = RelaySummary {
+            pending,
+            ..RelaySummary::default()
+        };
+        outdated.extend(summary);
+        return Ok((response, outdated));
+    }
+
+    // Collects the submitted rates of every symbol and clears the round
+    let mut rates: BTreeMap<String, Vec<Uint64>> = BTreeMap::new();
+    for relayer in &round.relayers {
+        let key = (round_key.0, round_key.1, relayer);
+        for (symbol, rate) in ROUND_SUBMISSIONS.load(deps.storage, key)? {
+            rates.entry(symbol).or_default().push(rate);
+        }
+        ROUND_SUBMISSIONS.remove(deps.storage, key);
+    }
+    PENDING_ROUNDS.remove(deps.storage, round_key);
+
+    // Only symbols submitted by enough relayers are applied, the others are reported as skipped
+    let (symbol_rates, dropped): (Vec<_>, Vec<_>) = rates
+        .into_iter()
+        .partition(|(_, rates)| rates.len() as u32 >= config.relay_threshold);
+    let symbol_rates = symbol_rates
+        .into_iter()
+        .map(|(symbol, rates)| (symbol, median(rates)))
+        .collect();
+
+    let (mut response, mut summary) = save_symbol_rates(
+        deps,
+        config,
+        response.add_attribute("round_finalized", "true"),
+        &round.relayers,
+        symbol_rates,
+        resolve_time,
+        request_id,
+    )?;
+    for (symbol, rates) in dropped {
+        let event = relay_event(&round.relayers, &symbol, resolve_time, request_id, false)
+            .add_attribute("status", "skipped")
+            .add_attribute("reason", SkipReason::InsufficientQuorum.as_str())
+            .add_attribute("submissions", rates.len().to_string());
+        response = response.add_event(event);
+        summary.skip(symbol, SkipReason::InsufficientQuorum);
+    }
+    outdated.extend(summary);
+
+    Ok((response, outdated))
+}
+
+// Returns the median of a non-empty vector of rates, averaging the middle pair of an even count
+fn median(mut rates: Vec<Uint64>) -> Uint64 {
+    rates.sort();
+    let mid = rates.len() / 2;
+    if rates.len() % 2 == 1 {
+        rates[mid]
+    } else {
+        let sum = u128::from(rates[mid - 1].u64()) + u128::from(rates[mid].u64());
+        Uint64::new((sum / 2) as u64)
+    }
+}
+
+// Saves the symbol rates that pass the resolve time and deviation guards
+fn save_symbol_rates(
+    deps: DepsMut,
+    config: &Config,
+    mut response: Response,
+    relayers: &[Addr],
+    symbol_rates: Vec<(String, Uint64)>,
+    resolve_time: Uint64,
+    request_id: Uint64,
+) -> Result<(Response, RelaySummary), ContractError> {
+    let mut summary = RelaySummary::default();
+    for (symbol, rate) in symbol_rates {
+        let event = relay_event(relayers, &symbol, resolve_time, request_id, false);
+        let existing_refdata = REFDATA.may_load(deps.storage, &symbol)?;
+        if let Some(reason) = relay_skip_reason(
+            deps.storage,
+            config,
+            &symbol,
+            rate,
+            resolve_time,
+            existing_refdata.as_ref(),
+        )? {
+            let event = event
+                .add_attribute("status", "skipped")
+                .add_attribute("reason", reason.as_str())
+                .add_attribute("rate", rate);
+            response = response.add_event(event);
+            response = match reason {
+                SkipReason::Paused => response.add_attribute("symbol_paused", &symbol),
+                SkipReason::Disabled => response.add_attribute("symbol_disabled", &symbol),
+                SkipReason::Unregistered => response.add_attribute("symbol_unregistered", &symbol),
+                SkipReason::DeviationExceeded => {
+                    response.add_attribute("deviation_exceeded", &symbol)
+                }
+                SkipReason::Outdated | SkipReason::RateLimited | SkipReason::InsufficientQuorum => {
+                    response
+                }
+            };
+            summary.skip(symbol, reason);
+            continue;
+        }
+
+        save_refdata(
+            deps.storage,
+            config,
+            &symbol,
+            &RefData::new(rate, resolve_time, request_id),
+        )?;
+        response = response.add_event(updated_event(event, existing_refdata.as_ref(), rate));
+        summary.updated.push(symbol);
+    }
+
+    Ok((response, summary))
+}
+
+// Returns the reason a relayed rate must not be saved, if any
+fn relay_skip_reason(
+    storage: &dyn Storage,
+    config: &Config,
+    symbol: &str,
+    rate: Uint64,
+    resolve_time: Uint64,
+    existing_refdata: Option<&RefData>,
+) -> StdResult<Option<SkipReason>> {
+    if is_paused(storage, symbol)? {
+        return Ok(Some(SkipReason::Paused));
+    }
+
+    // Rejects disabled symbols and, if required, unregistered symbols
+    match SYMBOLS.may_load(storage, symbol)? {
+        Some(symbol_info) if !symbol_info.enabled => return Ok(Some(SkipReason::Disabled)),
+        None if config.require_registered_symbols => return Ok(Some(SkipReason::Unregistered)),
+        _ => {}
+    }
+
+    if let Some(existing_refdata) = existing_refdata {
+        if existing_refdata.resolve_time >= resolve_time {
+            return Ok(Some(SkipReason::Outdated));
+        }
+
+        // Skips updates arriving faster than the minimum update interval
+        let symbol_config = SYMBOL_CONFIGS
+            .may_load(storage, symbol)?
+            .unwrap_or_default();
+        let min_update_interval_seconds = symbol_config
+            .min_update_interval_seconds
+            .unwrap_or(config.min_update_interval_seconds);
+        if resolve_time
+            < existing_refdata
+                .resolve_time
+                .saturating_add(min_update_interval_seconds)
+        {
+            return Ok(Some(SkipReason::RateLimited));
+        }
+
+        // Rejects rates that move too far from the stored rate
+        let max_deviation_bps = symbol_config
+            .max_deviation_bps
+            .unwrap_or(config.max_deviation_bps);
+        if exceeds_deviation(existing_refdata.rate, rate, max_deviation_bps) {
+            return Ok(Some(SkipReason::DeviationExceeded));
+        }
+    }
+
+    Ok(None)
+}
+
+// Creates the versioned event describing the outcome of relaying a single symbol, attributed to
+// a comma-separated list of the sender or, for a finalized round, every relayer of the round
+fn relay_event(
+    relayers: &[Addr],
+    symbol: &str,
+    resolve_time: Uint64,
+    request_id: Uint64,
+    forced: bool,
+) -> Event {
+    let relayers = relayers
+        .iter()
+        .map(Addr::as_str)
+        .collect::<Vec<&str>>()
+        .join(",");
+    Event::new(RELAY_EVENT)
+        .add_attribute("schema_version", RELAY_EVENT_SCHEMA_VERSION)
+        .add_attribute("relayers", relayers)
+        .add_attribute("symbol", symbol)
+        .add_attribute("resolve_time", resolve_time)
+        .add_attribute("request_id", request_id)
+        .add_attribute("forced", forced.to_string())
+}
+
+// Marks a relay event as updated with the previous and the new rate
+fn updated_event(event: Event, existing_refdata: Option<&RefData>, rate: Uint64) -> Event {
+    let event = event.add_attribute("status", "updated");
+    let event = match existing_refdata {
+        Some(existing_refdata) => event.add_attribute("previous_rate", existing_refdata.rate),
+        None => event,
+    };
+    event.add_attribute("rate", rate)
+}
+
+fn execute_force_relay(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    symbol_rates: Vec<(String, Uint64)>,
+    resolve_time: Uint64,
+    request_id: Uint64,
+    strict: bool,
+) -> Result<Response, ContractError> {
+    // Checks if sender is a force relayer
+    assert_role(deps.as_ref(), &info.sender, Role::ForceRelayer)?;
+    assert_not_paused(deps.storage)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    validate_symbol_rates(deps.storage, &config, &symbol_rates)?;
+    assert_symbols_permitted(deps.storage, &info.sender, &symbol_rates)?;
+    validate_resolve_time(&env, &config, resolve_time)?;
+
+    let mut response = Response::default().add_attribute("action", "execute_force_relay");
+    let mut summary = RelaySummary::default();
+    for (symbol, rate) in symbol_rates {
+        let event = relay_event(
+            slice::from_ref(&info.sender),
+            &symbol,
+            resolve_time,
+            request_id,
+            true,
+        );
+        if is_paused(deps.storage, &symbol)? {
+            let event = event
+                .add_attribute("status", "skipped")
+                .add_attribute("reason", SkipReason::Paused.as_str())
+                .add_attribute("rate", rate);
+            response = response
+                .add_event(event)
+                .add_attribute("symbol_paused", symbol.clone());
+            summary.skip(symbol, SkipReason::Paused);
+            continue;
+        }
+
+        let existing_refdata = REFDATA.may_load(deps.storage, &symbol)?;
+        save_refdata(
+            deps.storage,
+            &config,
+            &symbol,
+            &RefData::new(rate, resolve_time, request_id),
+        )?;
+        response = response.add_event(updated_event(event, existing_refdata.as_ref(), rate));
+        summary.updated.push(symbol);
+    }
+
+    set_relay_summary(response, summary, strict)
+}
+
+fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    update: ConfigUpdate,
+) -> Result<Response, ContractError> {
+    // Checks if sender is a config manager
+    assert_role(deps.as_ref(), &info.sender, Role::ConfigManager)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    if let Some(history_depth) = update.history_depth {
+        config.history_depth = history_depth;
+    }
+    if let Some(max_deviation_bps) = update.max_deviation_bps {
+        config.max_deviation_bps = max_deviation_bps;
+    }
+    if let Some(relay_threshold) = update.relay_threshold {
+        // Rejects thresholds that the current relayers can never reach
+        let relayers = RELAYERS
+            .keys_raw(deps.storage, None, None, Order::Ascending)
+            .count();
+        if relay_threshold as usize > relayers.max(1) {
+            return Err(ContractError::InvalidConfig {
+                msg: format!(
+                    "Relay threshold {} exceeds the number of relayers {}",
+                    relay_threshold, relayers
+                ),
+            });
+        }
+        config.relay_threshold = relay_threshold;
+    }
+    if let Some(require_registered_symbols) = update.require_registered_symbols {
+        config.require_registered_symbols = require_registered_symbols;
+    }
+    if let Some(anchor) = update.anchor {
+        config.anchor = anchor;
+    }
+    if let Some(max_symbols_per_relay) = update.max_symbols_per_relay {
+        config.max_symbols_per_relay = max_symbols_per_relay;
+    }
+    if let Some(max_future_drift_seconds) = update.max_future_drift_seconds {
+        config.max_future_drift_seconds = max_future_drift_seconds;
+    }
+    if let Some(max_resolve_age_seconds) = update.max_resolve_age_seconds {
+        config.max_resolve_age_seconds = max_resolve_age_seconds;
+    }
+    if let Some(reject_reused_request_ids) = update.reject_reused_request_ids {
+        config.reject_reused_request_ids = reject_reused_request_ids;
+    }
+    if let Some(min_update_interval_seconds) = update.min_update_interval_seconds {
+        config.min_update_interval_seconds = min_update_interval_seconds;
+    }
+    if let Some(timelock_delay_seconds) = update.timelock_delay_seconds {
+        // Checks if sender is admin, as the delay guards every other role
+        ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+        if timelock_delay_seconds > MAX_TIMELOCK_DELAY_SECONDS {
+            return Err(ContractError::TimelockDelayTooLong {
+                delay: timelock_delay_seconds,
+                max: MAX_TIMELOCK_DELAY_SECONDS,
+            });
+        }
+        config.timelock_delay_seconds = timelock_delay_seconds;
+    }
+    // Reused request IDs are detected through the request index, which is kept with the history
+    if config.reject_reused_request_ids && config.history_depth == 0 {
+        return Err(ContractError::InvalidConfig {
+            msg: String::from("reject_reused_request_ids requires a non-zero history_depth"),
+        });
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+fn execute_register_symbols(
+    deps: DepsMut,
+    info: MessageInfo,
+    symbols: Vec<(String, SymbolInfo)>,
+) -> Result<Response, ContractError> {
+    // Checks if sender is a config manager
+    assert_role(deps.as_ref(), &info.sender, Role::ConfigManager)?;
+
+    for (symbol, symbol_info) in symbols {
+        SYMBOLS.save(deps.storage, &symbol, &symbol_info)?;
+    }
+
+    Ok(Response::new().add_attribute("action", "register_symbols"))
+}
+
+fn execute_deregister_symbols(
+    deps: DepsMut,
+    info: MessageInfo,
+    symbols: Vec<String>,
+) -> Result<Response, ContractError> {
+    // Checks if sender is a config manager
+    assert_role(deps.as_ref(), &info.sender, Role::ConfigManager)?;
+
+    for symbol in symbols {
+        SYMBOLS.remove(deps.storage, &symbol);
+    }
+
+    Ok(Response::new().add_attribute("action", "deregister_symbols"))
+}
+
+fn execute_set_aliases(
+    deps: DepsMut,
+    info: MessageInfo,
+    aliases: Vec<(String, String)>,
+) -> Result<Response, ContractError> {
+    // Checks if sender is a config manager
+    assert_role(deps.as_ref(), &info.sender, Role::ConfigManager)?;
+
+    for (alias, symbol) in aliases {
+        ALIASES.save(deps.storage, &alias, &symbol)?;
+    }
+
+    Ok(Response::new().add_attribute("action", "set_aliases"))
+}
+
+fn execute_remove_aliases(
+    deps: DepsMut,
+    info: MessageInfo,
+    aliases: Vec<String>,
+) -> Result<Response, ContractError> {
+    // Checks if sender is a config manager
+    assert_role(deps.as_ref(), &info.sender, Role::ConfigManager)?;
+
+    for alias in aliases {
+        ALIASES.remove(deps.storage, &alias);
+    }
+
+    Ok(Response::new().add_attribute("action", "remove_aliases"))
+}
+
+fn execute_set_symbol_ids(
+    deps: DepsMut,
+    info: MessageInfo,
+    symbol_ids: Vec<(u16, String)>,
+) -> Result<Response, ContractError> {
+    // Checks if sender is a config manager
+    assert_role(deps.as_ref(), &info.sender, Role::ConfigManager)?;
+
+    for (id, symbol) in symbol_ids {
+        SYMBOL_IDS.save(deps.storage, id, &symbol)?;
+    }
+
+    Ok(Response::new().add_attribute("action", "set_symbol_ids"))
+}
+
+fn execute_remove_symbol_ids(
+    deps: DepsMut,
+    info: MessageInfo,
+    ids: Vec<u16>,
+) -> Result<Response, ContractError> {
+    // Checks if sender is a config manager
+    assert_role(deps.as_ref(), &info.sender, Role::ConfigManager)?;
+
+    for id in ids {
+        SYMBOL_IDS.remove(deps.storage, id);
+    }
+
+    Ok(Response::new().add_attribute("action", "remove_symbol_ids"))
+}
+
+fn execute_set_symbol_configs(
+    deps: DepsMut,
+    info: MessageInfo,
+    symbol_configs: Vec<(String, SymbolConfig)>,
+) -> Result<Response, ContractError> {
+    // Checks if sender is a config manager
+    assert_role(deps.as_ref(), &info.sender, Role::ConfigManager)?;
+
+    for (symbol, symbol_config) in symbol_configs {
+        SYMBOL_CONFIGS.save(deps.storage, &symbol, &symbol_config)?;
+    }
+
+    Ok(Response::new().add_attribute("action", "set_symbol_configs"))
+}
+
+fn execute_remove_symbol_configs(
+    deps: DepsMut,
+    info: MessageInfo,
+    symbols: Vec<String>,
+) -> Result<Response, ContractError> {
+    // Checks if sender is a config manager
+    assert_role(deps.as_ref(), &info.sender, Role::ConfigManager)?;
+
+    for symbol in symbols {
+        SYMBOL_CONFIGS.remove(deps.storage, &symbol);
+    }
+
+    Ok(Response::new().add_attribute("action", "remove_symbol_configs"))
+}
+
+fn execute_schedule_operation(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    // Checks if sender is admin
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    if is_timelock_msg(&msg) || is_multisig_msg(&msg) {
+        return Err(ContractError::InvalidOperation {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let id = NEXT_OPERATION_ID
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    NEXT_OPERATION_ID.save(deps.storage, &(id + 1))?;
+    let eta = Uint64::new(env.block.time.seconds()).checked_add(config.timelock_delay_seconds)?;
+    TIMELOCK_OPERATIONS.save(
+        deps.storage,
+        id,
+        &TimelockOperation {
+            id: Uint64::new(id),
+            msg,
+            eta,
+            proposer: info.sender,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "schedule_operation")
+        .add_attribute("id", id.to_string())
+        .add_attribute("eta", eta))
+}
+
+fn execute_execute_operation(
+    deps: DepsMut,
+    env: Env,
+    id: Uint64,
+) -> Result<Response, ContractError> {
+    let operation = TIMELOCK_OPERATIONS.load(deps.storage, id.u64())?;
+    if Uint64::new(env.block.time.seconds()) < operation.eta {
+        return Err(ContractError::OperationNotReady {
+            id,
+            eta: operation.eta,
+        });
+    }
+    TIMELOCK_OPERATIONS.remove(deps.storage, id.u64());
+
+    // Executes the operation on behalf of the current admin
+    let admin = ADMIN
+        .get(deps.as_ref())?
+        .ok_or(ContractError::Unauthorized {
+            msg: String::from("Contract has no admin"),
+        })?;
+    let response = dispatch(
+        deps,
+        env,
+        MessageInfo {
+            sender: admin,
+            funds: vec![],
+        },
+        operation.msg,
+    )?;
+
+    Ok(response.add_attribute("operation_id", id))
+}
+
+fn execute_cancel_operation(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: Uint64,
+) -> Result<Response, ContractError> {
+    // Checks if sender is admin
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    // Checks that the operation exists
+    TIMELOCK_OPERATIONS.load(deps.storage, id.u64())?;
+    TIMELOCK_OPERATIONS.remove(deps.storage, id.u64());
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_operation")
+        .add_attribute("id", id))
+}
+
+fn execute_configure_multisig(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    signers: Vec<(String, u64)>,
+    threshold: u64,
+) -> Result<Response, ContractError> {
+    // Checks if sender is admin
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let mut validated: Vec<(Addr, u64)> = vec![];
+    for (signer, weight) in signers {
+        let signer = deps.api.addr_validate(&signer)?;
+        if weight == 0 {
+            return Err(ContractError::InvalidMultisig {
+                msg: format!("Signer {} has no weight", signer),
+            });
+        }
+        if validated.iter().any(|(address, _)| *address == signer) {
+            return Err(ContractError::InvalidMultisig {
+                msg: format!("Duplicate signer {}", signer),
+            });
+        }
+        validated.push((signer, weight));
+    }
+    let total_weight = validated
+        .iter()
+        .try_fold(0u64, |total, (_, weight)| total.checked_add(*weight))
+        .ok_or(ContractError::InvalidMultisig {
+            msg: String::from("Total weight overflows"),
+        })?;
+    if threshold == 0 || threshold > total_weight {
+        return Err(ContractError::InvalidMultisig {
+            msg: format!(
+                "Threshold {} must be between 1 and the total weight {}",
+                threshold, total_weight
+            ),
+        });
+    }
+
+    // Transfers admin authority to the contract, which only acts through proposals, and drops
+    // any admin proposal made before the transfer
+    PENDING_ADMIN.remove(deps.storage);
+    MULTISIG.save(
+        deps.storage,
+        &Multisig {
+            signers: validated,
+            threshold,
+        },
+    )?;
+    ADMIN.set(deps, Some(env.contract.address))?;
+
+    Ok(Response::new().add_attribute("action", "configure_multisig"))
+}
+
+// Returns the weight of a multisig signer, or Unauthorized if the address is not a signer
+fn signer_weight(storage: &dyn Storage, address: &Addr) -> Result<u64, ContractError> {
+    MULTISIG
+        .may_load(storage)?
+        .and_then(|multisig| multisig.weight(address))
+        .ok_or(ContractError::Unauthorized {
+            msg: String::from("Sender is not a multisig signer"),
+        })
+}
+
+// Loads a proposal that has been neither executed nor expired
+fn load_open_proposal(
+    storage: &dyn Storage,
+    env: &Env,
+    proposal_id: Uint64,
+) -> Result<Proposal, ContractError> {
+    let proposal = PROPOSALS.load(storage, proposal_id.u64())?;
+    let expired = matches!(
+        proposal.expires_at,
+        Some(expires_at) if env.block.time.seconds() >= expires_at.u64()
+    );
+    if proposal.executed || expired {
+        return Err(ContractError::ProposalClosed { proposal_id });
+    }
+
+    Ok(proposal)
+}
+
+fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+    expires_at: Option<Uint64>,
+) -> Result<Response, ContractError> {
+    // Checks if sender is a signer
+    signer_weight(deps.storage, &info.sender)?;
+
+    if is_multisig_msg(&msg) {
+        return Err(ContractError::InvalidOperation {});
+    }
+
+    let id = NEXT_PROPOSAL_ID.may_load(deps.storage)?.unwrap_or_default();
+    if matches!(expires_at, Some(expires_at) if env.block.time.seconds() >= expires_at.u64()) {
+        return Err(ContractError::ProposalClosed {
+            proposal_id: Uint64::new(id),
+        });
+    }
+    NEXT_PROPOSAL_ID.save(deps.storage, &(id + 1))?;
+    PROPOSALS.save(
+        deps.storage,
+        id,
+        &Proposal {
+            id: Uint64::new(id),
+            msg,
+            proposer: info.sender.clone(),
+            expires_at,
+            executed: false,
+        },
+    )?;
+    VOTES.save(deps.storage, (id, &info.sender), &Vote::Yes)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "propose")
+        .add_attribute("proposal_id", id.to_string()))
+}
+
+fn execute_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: Uint64,
+    vote: Vote,
+) -> Result<Response, ContractError> {
+    // Checks if sender is a signer
+    signer_weight(deps.storage, &info.sender)?;
+
+    load_open_proposal(deps.storage, &env, proposal_id)?;
+    let key = (proposal_id.u64(), &info.sender);
+    if VOTES.has(deps.storage, key) {
+        return Err(ContractError::AlreadyVoted {
+            signer: info.sender,
+            proposal_id,
+        });
+    }
+    VOTES.save(deps.storage, key, &vote)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "vote")
+        .add_attribute("proposal_id", proposal_id))
+}
+
+fn execute_execute_proposal(
+    deps: DepsMut,
+    env: Env,
+    proposal_id: Uint64,
+) -> Result<Response, ContractError> {
+    let mut proposal = load_open_proposal(deps.storage, &env, proposal_id)?;
+
+    // Tallies the yes votes with the current signer weights
+    let multisig = MULTISIG.load(deps.storage)?;
+    let mut yes_weight = 0u64;
+    for item in VOTES
+        .prefix(proposal_id.u64())
+        .range(deps.storage, None, None, Order::Ascending)
+    {
+        let (signer, vote) = item?;
+        if vote == Vote::Yes {
+            yes_weight = yes_weight.saturating_add(multisig.weight(&signer).unwrap_or_default());
+        }
+    }
+    if yes_weight < multisig.threshold {
+        return Err(ContractError::ProposalNotPassed { proposal_id });
+    }
+    proposal.executed = true;
+    PROPOSALS.save(deps.storage, proposal_id.u64(), &proposal)?;
+
+    // Executes the proposal with the contract as sender, subject to the timelock
+    let info = MessageInfo {
+        sender: env.contract.address.clone(),
+        funds: vec![],
+    };
+    let response = execute(deps, env, info, proposal.msg)?;
+
+    Ok(response.add_attribute("proposal_id", proposal_id))
+}
+
+fn execute_remove_pending_rounds(
+    deps: DepsMut,
+    info: MessageInfo,
+    rounds: Vec<(Uint64, Uint64)>,
+) -> Result<Response, ContractError> {
+    // Checks if sender is a config manager
+    assert_role(deps.as_ref(), &info.sender, Role::ConfigManager)?;
+
+    for (request_id, resolve_time) in rounds {
+        let round_key = (request_id.u64(), resolve_time.u64());
+        if let Some(round) = PENDING_ROUNDS.may_load(deps.storage, round_key)? {
+            for relayer in &round.relayers {
+                ROUND_SUBMISSIONS.remove(deps.storage, (round_key.0, round_key.1, relayer));
+            }
+            PENDING_ROUNDS.remove(deps.storage, round_key);
+        }
+    }
+
+    Ok(Response::new().add_attribute("action", "remove_pending_rounds"))
+}
+
+// Checks if the change from the old rate to the new rate is more than max_deviation_bps
+fn exceeds_deviation(old_rate: Uint64, new_rate: Uint64, max_deviation_bps: u32) -> bool {
+    if max_deviation_bps == 0 || old_rate.is_zero() {
+        return false;
+    }
+
+    let old_rate = u128::from(old_rate.u64());
+    let new_rate = u128::from(new_rate.u64());
+    old_rate.abs_diff(new_rate) * BPS > old_rate * u128::from(max_deviation_bps)
+}
+
+// Returns whether any RefData kept in the history has been saved from the given request ID
+fn is_request_applied(storage: &dyn Storage, request_id: Uint64) -> bool {
+    REQUEST_REFS
+        .prefix(request_id.u64())
+        .keys_raw(storage, None, None, Order::Ascending)
+        .next()
+        .is_some()
+}
+
+// Saves the latest RefData of a symbol and appends it to the symbol's history and request index
+fn save_refdata(
+    storage: &mut dyn Storage,
+    config: &Config,
+    symbol: &str,
+    refdata: &RefData,
+) -> StdResult<()> {
+    REFDATA.save(storage, symbol, refdata)?;
+
+    let mut count = REF_HISTORY_COUNT
+        .may_load(storage, symbol)?
+        .unwrap_or_default();
+    if config.history_depth > 0 {
+        let key = (symbol, refdata.resolve_time.u64());
+        match REF_HISTORY.may_load(storage, key)? {
+            Some(replaced) => remove_request_ref(storage, symbol, &replaced)?,
+            None => count += 1,
+        }
+        REF_HISTORY.save(storage, key, refdata)?;
+        REQUEST_REFS.save(storage, (refdata.request_id.u64(), symbol), refdata)?;
+    }
+
+    // Prunes the oldest entries beyond the configured depth
+    if count > config.history_depth {
+        let expired = REF_HISTORY
+            .prefix(symbol)
+            .range(storage, None, None, Order::Ascending)
+            .take((count - config.history_depth) as usize)
+            .collect::<StdResult<Vec<(u64, RefData)>>>()?;
+        for (resolve_time, expired_refdata) in expired {
+            REF_HISTORY.remove(storage, (symbol, resolve_time));
+            remove_request_ref(storage, symbol, &expired_refdata)?;
+        }
+        count = config.history_depth;
+    }
+
+    if count > 0 {
+        REF_HISTORY_COUNT.save(storage, symbol, &count)
+    } else {
+        REF_HISTORY_COUNT.remove(storage, symbol);
+        Ok(())
+    }
+}
+
+// Removes the request index entry of a RefData leaving the history, unless a newer RefData of the
+// same request replaced it
+fn remove_request_ref(storage: &mut dyn Storage, symbol: &str, refdata: &RefData) -> StdResult<()> {
+    let key = (refdata.request_id.u64(), symbol);
+    if REQUEST_REFS.may_load(storage, key)?.as_ref() == Some(refdata) {
+        REQUEST_REFS.remove(storage, key);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    Ok(match msg {
+        QueryMsg::Admin {} => to_binary(&ADMIN.query_admin(deps)?),
+        QueryMsg::PendingAdmin {} => to_binary(&PENDING_ADMIN.may_load(deps.storage)?),
+        QueryMsg::IsRelayer { relayer } => {
+            to_binary(&query_is_relayer(deps, &deps.api.addr_validate(&relayer)?)?)
+        }
+        QueryMsg::ListRelayers { start_after, limit } => {
+            to_binary(&query_relayers(deps, start_after, limit)?)
+        }
+        QueryMsg::GetPendingRotation { relayer } => to_binary(
+            &PENDING_ROTATIONS.may_load(deps.storage, &deps.api.addr_validate(&relayer)?)?,
+        ),
+        QueryMsg::ListRelayerPermissions { start_after, limit } => {
+            to_binary(&query_relayer_permissions(deps, start_after, limit)?)
+        }
+        QueryMsg::HasRole { role, address } => to_binary(&ROLES.has(
+            deps.storage,
+            (role.as_str(), &deps.api.addr_validate(&address)?),
+        )),
+        QueryMsg::ListRoleMembers {
+            role,
+            start_after,
+            limit,
+        } => to_binary(&query_role_members(deps, role, start_after, limit)?),
+        QueryMsg::IsPaused { symbol } => to_binary(&match symbol {
+            Some(symbol) => is_paused(deps.storage, &symbol)?,
+            None => PAUSED.may_load(deps.storage)?.unwrap_or_default(),
+        }),
+        QueryMsg::GetRef { symbol } => to_binary(&query_ref(deps, &symbol)?),
+        QueryMsg::GetReferenceData {
+            symbol_pair,
+            max_age_seconds,
+        } => {
+            let reference_data = query_reference_data(deps, &symbol_pair)?;
+            assert_fresh(&env, &symbol_pair, &reference_data, max_age_seconds)?;
+            to_binary(&reference_data)
+        }
+        QueryMsg::GetReferenceDataBulk {
+            symbol_pairs,
+            max_age_seconds,
+        } => {
+            let reference_datas = query_reference_data_bulk(deps, &symbol_pairs)?;
+            for (symbol_pair, reference_data) in symbol_pairs.iter().zip(&reference_datas) {
+                assert_fresh(&env, symbol_pair, reference_data, max_age_seconds)?;
+            }
+            to_binary(&reference_datas)
+        }
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::GetSymbolConfig { symbol } => to_binary(
+            &SYMBOL_CONFIGS
+                .may_load(deps.storage, &symbol)?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::ListAliases { start_after, limit } => {
+            to_binary(&query_aliases(deps, start_after, limit)?)
+        }
+        QueryMsg::ListSymbolIds { start_after, limit } => {
+            to_binary(&query_symbol_ids(deps, start_after, limit)?)
+        }
+        QueryMsg::ListRefs { start_after, limit } => {
+            to_binary(&query_refs(deps, start_after, limit)?)
+        }
+        QueryMsg::ListSymbols { start_after, limit } => {
+            to_binary(&query_symbols(deps, start_after, limit)?)
+        }
+        QueryMsg::GetRefHistory {
+            symbol,
+            start_after,
+            limit,
+        } => to_binary(&query_ref_history(deps, &symbol, start_after, limit)?),
+        QueryMsg::GetRefsByRequestId {
+            request_id,
+            start_after,
+            limit,
+        } => to_binary(&query_refs_by_request_id(
+            deps,
+            request_id,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::GetOperation { id } => {
+            to_binary(&TIMELOCK_OPERATIONS.load(deps.storage, id.u64())?)
+        }
+        QueryMsg::ListOperations { start_after, limit } => {
+            to_binary(&query_operations(deps, start_after, limit)?)
+        }
+        QueryMsg::Multisig {} => to_binary(&MULTISIG.may_load(deps.storage)?),
+        QueryMsg::GetProposal { proposal_id } => {
+            to_binary(&PROPOSALS.load(deps.storage, proposal_id.u64())?)
+        }
+        QueryMsg::ListProposals { start_after, limit } => {
+            to_binary(&query_proposals(deps, start_after, limit)?)
+        }
+        QueryMsg::ListVotes {
+            proposal_id,
+            start_after,
+            limit,
+        } => to_binary(&query_votes(deps, proposal_id, start_after, limit)?),
+        QueryMsg::GetAuditLog { start_after, limit } => {
+            to_binary(&query_audit_log(deps, start_after, limit)?)
+        }
+        QueryMsg::ListPendingRounds { start_after, limit } => {
+            to_binary(&query_pending_rounds(deps, start_after, limit)?)
+        }
+        QueryMsg::GetRoundSubmissions {
+            request_id,
+            resolve_time,
+        } => to_binary(&query_round_submissions(deps, request_id, resolve_time)?),
+        QueryMsg::GetTwap {
+            symbol_pair,
+            window_seconds,
+        } => to_binary(&query_twap(deps, env, &symbol_pair, window_seconds)?),
+    }?)
+}
+
+fn query_is_relayer(deps: Deps, relayer: &Addr) -> StdResult<bool> {
+    Ok(RELAYERS.has(deps.storage, relayer))
+}
+
+fn query_relayers(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(Addr, RelayerInfo)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|address| deps.api.addr_validate(&address))
+        .transpose()?;
+    let min = start_after.as_ref().map(Bound::exclusive);
+
+    RELAYERS
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+fn query_role_members(
+    deps: Deps,
+    role: Role,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Addr>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|address| deps.api.addr_validate(&address))
+        .transpose()?;
+    let min = start_after.as_ref().map(Bound::exclusive);
+
+    ROLES
+        .prefix(role.as_str())
+        .keys(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+fn query_ref(deps: Deps, symbol: &str) -> Result<RefData, ContractError> {
+    let symbol = resolve_alias(deps.storage, symbol)?;
+    assert_feed_active(deps, &symbol)?;
+
+    if symbol == CONFIG.load(deps.storage)?.anchor {
+        Ok(RefData::new(E9, Uint64::MAX, Uint64::zero()))
+    } else {
+        Ok(REFDATA.load(deps.storage, &symbol)?)
+    }
+}
+
+// Returns the symbol an alias points to, or the symbol itself if it is not an alias
+fn resolve_alias(storage: &dyn Storage, symbol: &str) -> StdResult<String> {
+    Ok(ALIASES
+        .may_load(storage, symbol)?
+        .unwrap_or_else(|| symbol.to_string()))
+}
+
+// Checks that a symbol is not paused so that consumers do not read a frozen price
+fn assert_feed_active(deps: Deps, symbol: &str) -> Result<(), ContractError> {
+    if is_paused(deps.storage, symbol)? {
+        return Err(ContractError::FeedPaused {
+            symbol: symbol.to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn query_reference_data(
+    deps: Deps,
+    symbol_pair: &(String, String),
+) -> Result<ReferenceData, ContractError> {
+    let base = query_ref(deps, &symbol_pair.0)?;
+    let quote = query_ref(deps, &symbol_pair.1)?;
+
+    Ok(ReferenceData::new(
+        Uint256::from(base.rate)
+            .checked_mul(E18)?
+            .checked_div(Uint256::from(quote.rate))?,
+        base.resolve_time,
+        quote.resolve_time,
+    ))
+}
+
+// Checks that neither asset of a pair was last updated more than max_age_seconds before the
+// current block time
+fn assert_fresh(
+    env: &Env,
+    symbol_pair: &(String, String),
+    reference_data: &ReferenceData,
+    max_age_seconds: Option<Uint64>,
+) -> Result<(), ContractError> {
+    let max_age_seconds = match max_age_seconds {
+        Some(max_age_seconds) => max_age_seconds,
+        None => return Ok(()),
+    };
+
+    let now = env.block.time.seconds();
+    for (symbol, resolve_time) in [
+        (&symbol_pair.0, reference_data.last_updated_base),
+        (&symbol_pair.1, reference_data.last_updated_quote),
+    ] {
+        if resolve_time.u64().saturating_add(max_age_seconds.u64()) < now {
+            return Err(ContractError::StalePrice {
+                symbol: symbol.clone(),
+                resolve_time,
+                max_age_seconds,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn query_reference_data_bulk(
+    deps: Deps,
+    symbol_pairs: &[(String, String)],
+) -> Result<Vec<ReferenceData>, ContractError> {
+    symbol_pairs
+        .iter()
+        .map(|pair| query_reference_data(deps, pair))
+        .collect()
+}
+
+fn query_aliases(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(String, String)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = start_after.as_deref().map(Bound::exclusive);
+
+    ALIASES
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+fn query_relayer_permissions(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(Addr, RelayerPermissions)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|address| deps.api.addr_validate(&address))
+        .transpose()?;
+    let min = start_after.as_ref().map(Bound::exclusive);
+
+    RELAYER_PERMISSIONS
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+fn query_symbol_ids(
+    deps: Deps,
+    start_after: Option<u16>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u16, String)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = start_after.map(Bound::exclusive);
+
+    SYMBOL_IDS
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+fn query_refs(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(String, RefData)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = start_after.as_deref().map(Bound::exclusive);
+
+    REFDATA
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+fn query_symbols(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<SymbolEntry>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = start_after.as_deref().map(Bound::exclusive);
+
+    SYMBOLS
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (symbol, info) = item?;
+            let ref_data = REFDATA.may_load(deps.storage, &symbol)?;
+            Ok(SymbolEntry {
+                symbol,
+                info,
+                ref_data,
+            })
+        })
+        .collect()
+}
+
+fn query_refs_by_request_id(
+    deps: Deps,
+    request_id: Uint64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(String, RefData)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = start_after.as_deref().map(Bound::exclusive);
+
+    REQUEST_REFS
+        .prefix(request_id.u64())
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+fn query_ref_history(
+    deps: Deps,
+    symbol: &str,
+    start_after: Option<Uint64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<RefData>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let max = start_after.map(|resolve_time| Bound::exclusive(resolve_time.u64()));
+
+    REF_HISTORY
+        .prefix(symbol)
+        .range(deps.storage, None, max, Order::Descending)
+        .take(limit)
+        .map(|item| item.map(|(_, refdata)| refdata))
+        .collect()
+}
+
+fn query_operations(
+    deps: Deps,
+    start_after: Option<Uint64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<TimelockOperation>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = start_after.map(|id| Bound::exclusive(id.u64()));
+
+    TIMELOCK_OPERATIONS
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, operation)| operation))
+        .collect()
+}
+
+fn query_proposals(
+    deps: Deps,
+    start_after: Option<Uint64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Proposal>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = start_after.map(|id| Bound::exclusive(id.u64()));
+
+    PROPOSALS
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, proposal)| proposal))
+        .collect()
+}
+
+fn query_votes(
+    deps: Deps,
+    proposal_id: Uint64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(Addr, Vote)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|address| deps.api.addr_validate(&address))
+        .transpose()?;
+    let min = start_after.as_ref().map(Bound::exclusive);
+
+    VOTES
+        .prefix(proposal_id.u64())
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+fn query_audit_log(
+    deps: Deps,
+    start_after: Option<Uint64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<AuditEntry>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = start_after.map(|seq| Bound::exclusive(seq.u64()));
+
+    AUDIT_LOG
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, entry)| entry))
+        .collect()
+}
+
+fn query_pending_rounds(
+    deps: Deps,
+    start_after: Option<(Uint64, Uint64)>,
+    limit: Option<u32>,
+) -> StdResult<Vec<PendingRound>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = start_after
+        .map(|(request_id, resolve_time)| Bound::exclusive((request_id.u64(), resolve_time.u64())));
+
+    PENDING_ROUNDS
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, round)| round))
+        .collect()
+}
+
+fn query_round_submissions(
+    deps: Deps,
+    request_id: Uint64,
+    resolve_time: Uint64,
+) -> StdResult<Vec<(Addr, SymbolRates)>> {
+    ROUND_SUBMISSIONS
+        .prefix((request_id.u64(), resolve_time.u64()))
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect()
+}
+
+// Returns the RefData of a symbol that were in effect between start and end in ascending order,
+// where the first entry was already in effect at start
+fn query_ref_observations(
+    deps: Deps,
+    symbol: &str,
+    start: u64,
+    end: u64,
+) -> Result<Vec<RefData>, ContractError> {
+    let symbol = resolve_alias(deps.storage, symbol)?;
+    if symbol == CONFIG.load(deps.storage)?.anchor {
+        return Ok(vec![query_ref(deps, &symbol)?]);
+    }
+    assert_feed_active(deps, &symbol)?;
+
+    let mut observations = vec![];
+    for item in REF_HISTORY.prefix(&symbol).range(
+        deps.storage,
+        None,
+        Some(Bound::inclusive(end)),
+        Order::Descending,
+    ) {
+        let (resolve_time, refdata) = item?;
+        observations.push(refdata);
+        if resolve_time <= start {
+            observations.reverse();
+            return Ok(observations);
+        }
+    }
+
+    Err(StdError::generic_err(format!(
+        "Not enough observations of {} to cover the TWAP window",
+        symbol
+    ))
+    .into())
+}
+
+fn query_twap(
+    deps: Deps,
+    env: Env,
+    symbol_pair: &(String, String),
+    window_seconds: Uint64,
+) -> Result<ReferenceData, ContractError> {
+    if window_seconds.is_zero() {
+        return Err(StdError::generic_err("TWAP window must be greater than zero").into());
+    }
+
+    let end = env.block.time.seconds();
+    let start = end.saturating_sub(window_seconds.u64());
+    let base = query_ref_observations(deps, &symbol_pair.0, start, end)?;
+    let quote = query_ref_observations(deps, &symbol_pair.1, start, end)?;
+
+    // Weights the pair rate of every interval between consecutive observations of either asset
+    // by the interval's duration
+    let mut cumulative = Uint256::zero();
+    let (mut i, mut j) = (0, 0);
+    let mut time = start;
+    while time < end {
+        let next_base = base.get(i + 1).map_or(end, |o| o.resolve_time.u64());
+        let next_quote = quote.get(j + 1).map_or(end, |o| o.resolve_time.u64());
+        let next = next_base.min(next_quote);
+
+        let rate = Uint256::from(base[i].rate)
+            .checked_mul(E18)?
+            .checked_div(Uint256::from(quote[j].rate))?;
+        cumulative = cumulative.checked_add(rate.checked_mul(Uint256::from(next - time))?)?;
+
+        if next == next_base {
+            i += 1;
+        }
+        if next == next_quote {
+            j += 1;
+        }
+        time = next;
+    }
+
+    Ok(ReferenceData::new(
+        cumulative.checked_div(Uint256::from(end - start))?,
+        base[base.len() - 1].resolve_time,
+        quote[quote.len() - 1].resolve_time,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{Addr, Uint256};
+
+    use crate::msg::ExecuteMsg::{AddRelayers, Relay};
+
+    use super::*;
+
+    // This function will setup the contract for other tests
+    fn setup(mut deps: DepsMut, sender: &str) {
+        let info = mock_info(sender, &[]);
+        let env = mock_env();
+        instantiate(deps.branch(), env, info, InstantiateMsg {}).unwrap();
+    }
+
+    fn is_relayers(deps: Deps, relayers: Vec<Addr>) -> Vec<bool> {
+        relayers
+            .iter()
+            .map(|r| query_is_relayer(deps, r))
+            .collect::<StdResult<Vec<bool>>>()
+            .unwrap()
+    }
+
+    // This function will setup the relayer for other tests
+    fn setup_relayers(mut deps: DepsMut, sender: &str, relayers: Vec<String>) {
+        setup(deps.branch(), sender);
+
+        let info = mock_info(sender, &[]);
+        let env = mock_env();
+        let msg = AddRelayers {
+            relayers: relayers.clone(),
+        };
+        execute(deps.branch(), env, info, msg).unwrap();
+    }
+
+    // This function will update the config through UpdateConfig for other tests
+    fn update_config(deps: DepsMut, update: ConfigUpdate) {
+        let info = mock_info("owner", &[]);
+        let env = mock_env();
+        execute(deps, env, info, ExecuteMsg::UpdateConfig(update)).unwrap();
+    }
+
+    // This function will grant a role to an address for other tests
+    fn grant_role(deps: DepsMut, role: Role, address: &str) {
+        let info = mock_info("owner", &[]);
+        let env = mock_env();
+        let msg = ExecuteMsg::GrantRole {
+            role,
+            addresses: vec![address.to_string()],
+        };
+        execute(deps, env, info, msg).unwrap();
+    }
+
+    // This function will setup mock relays for other tests
+    fn setup_relays(
+        mut deps: DepsMut,
+        sender: &str,
+        relayers: Vec<String>,
+        symbol_rates: Vec<(String, Uint64)>,
+        resolve_time: Uint64,
+        request_id: Uint64,
+    ) {
+        setup_relayers(deps.branch(), sender, relayers.clone());
+
+        let info = mock_info(relayers[0].as_str(), &[]);
+        let env = mock_env();
+
+        let msg = Relay {
+            symbol_rates,
+            resolve_time,
+            request_id,
+            strict: None,
+        };
+        execute(deps.branch(), env, info, msg).unwrap();
+    }
+
+    // This function will relay the given symbols through Relay for other tests
+    fn relay(
+        deps: DepsMut,
+        relayer: &str,
+        symbol_rates: Vec<(&str, u64)>,
+        resolve_time: u64,
+        request_id: u64,
+    ) -> Result<Response, ContractError> {
+        let msg = Relay {
+            symbol_rates: symbol_rates
+                .into_iter()
+                .map(|(symbol, rate)| (symbol.to_string(), Uint64::new(rate)))
+                .collect(),
+            resolve_time: Uint64::new(resolve_time),
+            request_id: Uint64::new(request_id),
+            strict: None,
+        };
+        execute(deps, mock_env(), mock_info(relayer, &[]), msg)
+    }
+
+    // This function will update the history depth for other tests
+    fn set_history_depth(deps: DepsMut, history_depth: u32) {
+        update_config(
+            deps,
+            ConfigUpdate {
+                history_depth: Some(history_depth),
+                ..ConfigUpdate::default()
+            },
+        );
+    }
+
+    // This function will build an AddRelayers message for other tests
+    fn add_relayer_msg() -> ExecuteMsg {
+        AddRelayers {
+            relayers: vec![String::from("relayer")],
+        }
+    }
+
+    mod instantiate {
+        use super::*;
+
+        #[test]
+        fn can_instantiate() {
+            let mut deps = mock_dependencies();
+            let init_msg = InstantiateMsg {};
+            let info = mock_info("owner", &[]);
+            let env = mock_env();
+            let res = instantiate(deps.as_mut(), env, info.clone(), init_msg).unwrap();
+            assert_eq!(0, res.messages.len());
+            assert!(ADMIN.is_admin(deps.as_ref(), &info.sender).unwrap());
+        }
+    }
+
+    mod relay {
+        use std::iter::zip;
+
+        use cosmwasm_std::{attr, from_binary};
+
+        use crate::msg::ExecuteMsg::{AddRelayers, ForceRelay, Relay, RelayBatch, RemoveRelayers};
+
+        use super::*;
+
+        #[test]
+        fn add_relayers_by_owner() {
+            // Setup
+            let mut deps = mock_dependencies();
+            let init_msg = InstantiateMsg {};
+            let info = mock_info("owner", &[]);
+            let env = mock_env();
+            instantiate(deps.as_mut(), env.clone(), info, init_msg).unwrap();
+            let relayers_to_add: Vec<String> = vec![
+                String::from("relayer_1"),
+                String::from("relayer_2"),
+                String::from("relayer_3"),
+            ];
+
+            // Test authorized attempt to add relayers
+            let info = mock_info("owner", &[]);
+            let env = mock_env();
+            let msg = AddRelayers {
+                relayers: relayers_to_add.clone(),
+            };
+            execute(deps.as_mut(), env, info, msg).unwrap();
+
+            assert_eq!(
+                is_relayers(
+                    deps.as_ref(),
+                    relayers_to_add
+                        .iter()
+                        .map(Addr::unchecked)
+                        .collect::<Vec<Addr>>(),
+                ),
+                [true, true, true]
+            );
+        }
+
+        #[test]
+        fn add_relayers_by_other() {
+            // Setup
+            let mut deps = mock_dependencies();
+            let init_msg = InstantiateMsg {};
+            let info = mock_info("owner", &[]);
+            let env = mock_env();
+            instantiate(deps.as_mut(), env.clone(), info, init_msg).unwrap();
+
+            // Test unauthorized attempt to add relayer
+            let info = mock_info("user", &[]);
+            let env = mock_env();
+            let msg = AddRelayers {
+                relayers: vec![String::from("relayer_1")],
+            };
+            let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Unauthorized {
+                    msg: String::from("Sender does not have the relayer_manager role")
+                }
+            );
+        }
+
+        #[test]
+        fn remove_relayers_by_owner() {
+            // Setup
+            let mut deps = mock_dependencies();
+            let relayers_list = vec![
+                String::from("relayer_1"),
+                String::from("relayer_2"),
+                String::from("relayer_3"),
+            ];
+            setup_relayers(deps.as_mut(), "owner", relayers_list.clone());
+
+            // Remove relayer
+            let relayers_to_remove = relayers_list[..2].to_vec();
+            let info = mock_info("owner", &[]);
+            let env = mock_env();
+            let msg = RemoveRelayers {
+                relayers: relayers_to_remove,
+            };
+            execute(deps.as_mut(), env, info, msg).unwrap();
+
+            assert_eq!(
+                is_relayers(
+                    deps.as_ref(),
+                    relayers_list
+                        .into_iter()
+                        .map(Addr::unchecked)
+                        .collect::<Vec<Addr>>(),
+                ),
+                [false, false, true]
+            );
+        }
+
+        #[test]
+        fn remove_relayers_by_other() {
+            // Setup
+            let mut deps = mock_dependencies();
+            let relayers = vec![String::from("relayer_1")];
+            setup_relayers(deps.as_mut(), "owner", relayers.clone());
+
+            // Test unauthorized attempt to remove relayer
+            let info = mock_info("user", &[]);
+            let env = mock_env();
+            let msg = RemoveRelayers { relayers };
+            let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Unauthorized {
+                    msg: String::from("Sender does not have the relayer_manager role")
+                }
+            );
+        }
+
+        #[test]
+        fn attempt_relay_by_relayer() {
+            // Setup
+            let mut deps = mock_dependencies();
+            let relayer = String::from("relayer");
+            setup_relayers(deps.as_mut(), "owner", vec![relayer.clone()]);
+
+            // Test authorized attempt to relay data
+            let info = mock_info(relayer.as_str(), &[]);
+            let env = mock_env();
+            let symbols = vec!["AAA", "BBB", "CCC"]
+                .into_iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<String>>();
+            let rates = [1000, 2000, 3000]
+                .iter()
+                .map(|r| Uint64::new(*r))
+                .collect::<Vec<Uint64>>();
+
+            let msg = Relay {
+                symbol_rates: zip(symbols.clone(), rates.clone())
+                    .collect::<Vec<(String, Uint64)>>(),
+                resolve_time: Uint64::from(100u64),
+                request_id: Uint64::one(),
+                strict: None,
+            };
+            execute(deps.as_mut(), env, info, msg).unwrap();
+
+            // Check if relay was successful
+            let reference_datas = query_reference_data_bulk(
+                deps.as_ref(),
+                &symbols
+                    .clone()
+                    .iter()
+                    .map(|s| (s.clone(), String::from("USD")))
+                    .collect::<Vec<(String, String)>>(),
+            )
+            .unwrap();
+            let retrieved_rates = reference_datas
+                .clone()
+                .into_iter()
+                .map(|rd| rd.rate / Uint256::from(E9))
+                .collect::<Vec<Uint256>>();
+            assert_eq!(
+                retrieved_rates,
+                rates
+                    .iter()
+                    .map(|r| Uint256::from(*r))
+                    .collect::<Vec<Uint256>>()
+            );
+        }
+
+        #[test]
+        fn attempt_relay_by_relayer_with_invalid_resolve_time() {
+            // Setup
+            let mut deps = mock_dependencies();
+            let relayer = String::from("relayer");
+            setup_relayers(deps.as_mut(), "owner", vec![relayer.clone()]);
+
+            // Relay initial set of data
+            let info = mock_info(relayer.as_str(), &[]);
+            let env = mock_env();
+            let symbols = vec!["AAA", "BBB", "CCC"]
+                .into_iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<String>>();
+            let rates = [1000, 2000, 3000]
+                .iter()
+                .map(|r| Uint64::new(*r))
+                .collect::<Vec<Uint64>>();
+            let msg = Relay {
+                symbol_rates: zip(symbols.clone(), rates.clone())
+                    .collect::<Vec<(String, Uint64)>>(),
+                resolve_time: Uint64::from(100u64),
+                request_id: Uint64::one(),
+                strict: None,
+            };
+            execute(deps.as_mut(), env, info, msg).unwrap();
+
+            // Test attempt to relay with invalid resolve times
+            let info = mock_info(relayer.as_str(), &[]);
+            let env = mock_env();
+            let old_rates = [1, 2, 3]
+                .iter()
+                .map(|r| Uint64::new(*r))
+                .collect::<Vec<Uint64>>();
+            let msg = Relay {
+                symbol_rates: zip(symbols.clone(), old_rates).collect::<Vec<(String, Uint64)>>(),
+                resolve_time: Uint64::from(90u64),
+                request_id: Uint64::one(),
+                strict: None,
+            };
+            execute(deps.as_mut(), env, info, msg).unwrap();
+
+            // Check if relay was successful
+            let reference_datas = query_reference_data_bulk(
+                deps.as_ref(),
+                &symbols
+                    .clone()
+                    .iter()
+                    .map(|s| (s.clone(), String::from("USD")))
+                    .collect::<Vec<(String, String)>>(),
+            )
+            .unwrap();
+            let retrieved_rates = reference_datas
+                .clone()
+                .into_iter()
+                .map(|rd| rd.rate / Uint256::from(E9))
+                .collect::<Vec<Uint256>>();
+            assert_eq!(
+                retrieved_rates,
+                rates
+                    .iter()
+                    .map(|r| Uint256::from(*r))
+                    .collect::<Vec<Uint256>>()
+            );
+        }
+
+        #[test]
+        fn attempt_relay_by_relayer_with_partially_invalid_resolve_time() {
+            // Setup
+            let mut deps = mock_dependencies();
+            let relayer = String::from("relayer");
+            setup_relayers(deps.as_mut(), "owner", vec![relayer.clone()]);
+
+            // Relay initial set of data
+            let info = mock_info(relayer.as_str(), &[]);
+            let env = mock_env();
+            let symbols = vec!["AAA", "BBB", "CCC"]
+                .into_iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<String>>();
+            let rates = [1000, 2000, 3000]
+                .iter()
+                .map(|r| Uint64::new(*r))
+                .collect::<Vec<Uint64>>();
+            let msg = Relay {
+                symbol_rates: zip(symbols.clone(), rates).collect::<Vec<(String, Uint64)>>(),
+                resolve_time: Uint64::from(10u64),
+                request_id: Uint64::one(),
+                strict: None,
+            };
+            execute(deps.as_mut(), env, info, msg).unwrap();
+
+            // Only relay one symbol
+            let info = mock_info(relayer.as_str(), &[]);
+            let env = mock_env();
+            let msg = Relay {
+                symbol_rates: vec![(String::from("AAA"), Uint64::new(99999))],
+                resolve_time: Uint64::from(20u64),
+                request_id: Uint64::from(3u64),
+                strict: None,
+            };
+            execute(deps.as_mut(), env, info, msg).unwrap();
+
+            // Test attempt to relay with partially invalid resolve times
+            let info = mock_info(relayer.as_str(), &[]);
+            let env = mock_env();
+            let update_rates = [1, 2, 3]
+                .iter()
+                .map(|r| Uint64::new(*r))
+                .collect::<Vec<Uint64>>();
+            let msg = Relay {
+                symbol_rates: zip(symbols.clone(), update_rates).collect::<Vec<(String, Uint64)>>(),
+                resolve_time: Uint64::from(15u64),
+                request_id: Uint64::from(2u64),
+                strict: None,
+            };
+            execute(deps.as_mut(), env, info, msg).unwrap();
+
+            // Check if relay was successful
+            let reference_datas = query_reference_data_bulk(
+                deps.as_ref(),
+                &symbols
+                    .clone()
+                    .iter()
+                    .map(|s| (s.clone(), String::from("USD")))
+                    .collect::<Vec<(String, String)>>(),
+            )
+            .unwrap();
+            let retrieved_rates = reference_datas
+                .clone()
+                .into_iter()
+                .map(|rd| rd.rate / Uint256::from(E9))
+                .collect::<Vec<Uint256>>();
+            let expected_rates = [99999, 2, 3]
+                .iter()
+                .map(|r| Uint256::from(*r as u128))
+                .collect::<Vec<Uint256>>();
+            assert_eq!(retrieved_rates, expected_rates);
+        }
+
+        #[test]
+        fn attempt_relay_by_others() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup(deps.as_mut(), "owner");
+
+            // Test unauthorized attempt to relay data
+            let info = mock_info("user", &[]);
+            let env = mock_env();
+            let symbols = vec!["AAA", "BBB", "CCC"]
+                .into_iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<String>>();
+            let rates = [1000, 2000, 3000]
+                .iter()
+                .map(|r| Uint64::new(*r))
+                .collect::<Vec<Uint64>>();
+            let msg = Relay {
+                symbol_rates: zip(symbols.clone(), rates.clone())
+                    .collect::<Vec<(String, Uint64)>>(),
+                resolve_time: Uint64::zero(),
+                request_id: Uint64::zero(),
+                strict: None,
+            };
+            let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Unauthorized {
+                    msg: String::from("Sender is not a relayer")
+                }
+            );
+        }
+
+        #[test]
+        fn attempt_force_relay_by_relayer() {
+            // Setup
+            let mut deps = mock_dependencies();
+            let relayer = String::from("relayer");
+            setup_relayers(deps.as_mut(), "owner", vec![relayer.clone()]);
+            grant_role(deps.as_mut(), Role::ForceRelayer, &relayer);
+
+            // Test authorized attempt to relay data
+            let info = mock_info(relayer.as_str(), &[]);
+            let env = mock_env();
+            let symbols = vec!["AAA", "BBB", "CCC"]
+                .into_iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<String>>();
+            let rates = [1000, 2000, 3000]
+                .iter()
+                .map(|r| Uint64::new(*r))
+                .collect::<Vec<Uint64>>();
+            let msg = ForceRelay {
+                symbol_rates: zip(symbols.clone(), rates.clone())
+                    .collect::<Vec<(String, Uint64)>>(),
+                resolve_time: Uint64::from(100u64),
+                request_id: Uint64::from(2u64),
+                strict: None,
+            };
+            execute(deps.as_mut(), env, info, msg).unwrap();
+
+            // Test attempt to force relay
+            let info = mock_info(relayer.as_str(), &[]);
+            let env = mock_env();
+            let forced_rates = [1, 2, 3]
+                .iter()
+                .map(|r| Uint64::new(*r))
+                .collect::<Vec<Uint64>>();
+            let msg = ForceRelay {
+                symbol_rates: zip(symbols.clone(), forced_rates.clone())
+                    .collect::<Vec<(String, Uint64)>>(),
+                resolve_time: Uint64::from(90u64),
+                request_id: Uint64::one(),
+                strict: None,
+            };
+            execute(deps.as_mut(), env, info, msg).unwrap();
+
+            // Check if forced relay was successful
+            let reference_datas = query_reference_data_bulk(
+                deps.as_ref(),
+                &symbols
+                    .clone()
+                    .iter()
+                    .map(|s| (s.clone(), String::from("USD")))
+                    .collect::<Vec<(String, String)>>(),
+            )
+            .unwrap();
+            let retrieved_rates = reference_datas
+                .into_iter()
+                .map(|rd| rd.rate / Uint256::from(E9))
+                .collect::<Vec<Uint256>>();
+            assert_eq!(
+                retrieved_rates,
+                forced_rates
+                    .iter()
+                    .map(|r| Uint256::from(*r))
+                    .collect::<Vec<Uint256>>()
+            );
+        }
+
+        #[test]
+        fn attempt_force_relay_by_other() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup(deps.as_mut(), "owner");
+
+            // Test unauthorized attempt to relay data
+            let info = mock_info("user", &[]);
+            let env = mock_env();
+            let symbols = vec!["AAA", "BBB", "CCC"]
+                .into_iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<String>>();
+            let rates = [1000, 2000, 3000]
+                .iter()
+                .map(|r| Uint64::new(*r))
+                .collect::<Vec<Uint64>>();
+            let msg = ForceRelay {
+                symbol_rates: zip(symbols.clone(), rates.clone())
+                    .collect::<Vec<(String, Uint64)>>(),
+                resolve_time: Uint64::zero(),
+                request_id: Uint64::zero(),
+                strict: None,
+            };
+            let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Unauthorized {
+                    msg: String::from("Sender does not have the force_relayer role")
+                }
+            );
+        }
+
+        #[test]
+        fn relay_emits_symbol_events() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relays(
+                deps.as_mut(),
+                "owner",
+                vec![String::from("relayer")],
+                vec![(String::from("AAA"), Uint64::new(1000))],
+                Uint64::new(100),
+                Uint64::one(),
+            );
+
+            // Test updated and outdated symbols are reported
+            let msg = Relay {
+                symbol_rates: vec![
+                    (String::from("AAA"), Uint64::new(1100)),
+                    (String::from("BBB"), Uint64::new(2000)),
+                ],
+                resolve_time: Uint64::new(90),
+                request_id: Uint64::new(2),
+                strict: None,
+            };
+            let res = execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap();
+            assert_eq!(
+                res.events,
+                vec![
+                    Event::new("relay_symbol").add_attributes(vec![
+                        ("schema_version", "2"),
+                        ("relayers", "relayer"),
+                        ("symbol", "AAA"),
+                        ("resolve_time", "90"),
+                        ("request_id", "2"),
+                        ("forced", "false"),
+                        ("status", "skipped"),
+                        ("reason", "outdated"),
+                        ("rate", "1100"),
+                    ]),
+                    Event::new("relay_symbol").add_attributes(vec![
+                        ("schema_version", "2"),
+                        ("relayers", "relayer"),
+                        ("symbol", "BBB"),
+                        ("resolve_time", "90"),
+                        ("request_id", "2"),
+                        ("forced", "false"),
+                        ("status", "updated"),
+                        ("rate", "2000"),
+                    ]),
+                ]
+            );
+
+            // Test updated symbols include the previous rate
+            let msg = Relay {
+                symbol_rates: vec![(String::from("AAA"), Uint64::new(1100))],
+                resolve_time: Uint64::new(110),
+                request_id: Uint64::new(3),
+                strict: None,
+            };
+            let res = execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap();
+            assert_eq!(
+                res.events[0].attributes[6..],
+                [
+                    attr("status", "updated"),
+                    attr("previous_rate", "1000"),
+                    attr("rate", "1100"),
+                ]
+            );
+        }
+
+        #[test]
+        fn force_relay_emits_forced_events() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relays(
+                deps.as_mut(),
+                "owner",
+                vec![String::from("relayer")],
+                vec![(String::from("AAA"), Uint64::new(1000))],
+                Uint64::new(100),
+                Uint64::one(),
+            );
+
+            // Test forced updates are reported even for older resolve times
+            let msg = ForceRelay {
+                symbol_rates: vec![(String::from("AAA"), Uint64::new(900))],
+                resolve_time: Uint64::new(50),
+                request_id: Uint64::new(2),
+                strict: None,
+            };
+            let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+            assert_eq!(
+                res.events,
+                vec![Event::new("relay_symbol").add_attributes(vec![
+                    ("schema_version", "2"),
+                    ("relayers", "owner"),
+                    ("symbol", "AAA"),
+                    ("resolve_time", "50"),
+                    ("request_id", "2"),
+                    ("forced", "true"),
+                    ("status", "updated"),
+                    ("previous_rate", "1000"),
+                    ("rate", "900"),
+                ])]
+            );
+        }
+
+        #[test]
+        fn relay_rejects_invalid_symbol_rates() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relayers(deps.as_mut(), "owner", vec![String::from("relayer")]);
+            let msg = ExecuteMsg::SetAliases {
+                aliases: vec![(String::from("MATIC"), String::from("POL"))],
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+            // Test every malformed input is rejected by both Relay and ForceRelay
+            let cases = vec![
+                (
+                    vec![("AAA", 1000), ("AAA", 2000)],
+                    ContractError::DuplicateSymbol {
+                        symbol: String::from("AAA"),
+                    },
+                ),
+                (
+                    vec![("AAA", 0)],
+                    ContractError::ZeroRate {
+                        symbol: String::from("AAA"),
+                    },
+                ),
+                (
+                    vec![("", 1000)],
+                    ContractError::InvalidSymbol {
+                        symbol: String::new(),
+                    },
+                ),
+                (
+                    vec![("A A", 1000)],
+                    ContractError::InvalidSymbol {
+                        symbol: String::from("A A"),
+                    },
+                ),
+                (
+                    vec![("USD", 1000)],
+                    ContractError::ReservedSymbol {
+                        symbol: String::from("USD"),
+                    },
+                ),
+                (
+                    vec![("MATIC", 1000)],
+                    ContractError::ReservedSymbol {
+                        symbol: String::from("MATIC"),
+                    },
+                ),
+            ];
+            for (symbol_rates, expected) in cases {
+                let symbol_rates = symbol_rates
+                    .into_iter()
+                    .map(|(s, r)| (s.to_string(), Uint64::new(r)))
+                    .collect::<Vec<(String, Uint64)>>();
+                let msg = Relay {
+                    symbol_rates: symbol_rates.clone(),
+                    resolve_time: Uint64::new(100),
+                    request_id: Uint64::one(),
+                    strict: None,
+                };
+                let err =
+                    execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap_err();
+                assert_eq!(err, expected);
+
+                let msg = ForceRelay {
+                    symbol_rates,
+                    resolve_time: Uint64::new(100),
+                    request_id: Uint64::one(),
+                    strict: None,
+                };
+                let err =
+                    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
+                assert_eq!(err, expected);
+            }
+
+            // Test overly long symbols are rejected
+            let msg = Relay {
+                symbol_rates: vec![("A".repeat(33), Uint64::new(1000))],
+                resolve_time: Uint64::new(100),
+                request_id: Uint64::one(),
+                strict: None,
+            };
+            let err =
+                execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::InvalidSymbol {
+                    symbol: "A".repeat(33)
+                }
+            );
+        }
+
+        #[test]
+        fn relay_too_many_symbols() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relayers(deps.as_mut(), "owner", vec![String::from("relayer")]);
+            update_config(
+                deps.as_mut(),
+                ConfigUpdate {
+                    max_symbols_per_relay: Some(2),
+                    ..ConfigUpdate::default()
+                },
+            );
+
+            // Test messages above the limit are rejected
+            let symbol_rates = vec![("AAA", 1000), ("BBB", 2000), ("CCC", 3000)]
+                .into_iter()
+                .map(|(s, r)| (s.to_string(), Uint64::new(r)))
+                .collect::<Vec<(String, Uint64)>>();
+            let msg = Relay {
+                symbol_rates: symbol_rates.clone(),
+                resolve_time: Uint64::new(100),
+                request_id: Uint64::one(),
+                strict: None,
+            };
+            let err =
+                execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::TooManySymbols { count: 3, max: 2 });
+
+            // Test messages within the limit are accepted
+            let msg = Relay {
+                symbol_rates: symbol_rates[..2].to_vec(),
+                resolve_time: Uint64::new(100),
+                request_id: Uint64::one(),
+                strict: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap();
+        }
+
+        #[test]
+        fn relay_resolve_time_bounds() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relayers(deps.as_mut(), "owner", vec![String::from("relayer")]);
+            update_config(
+                deps.as_mut(),
+                ConfigUpdate {
+                    max_future_drift_seconds: Some(Uint64::new(60)),
+                    max_resolve_age_seconds: Some(Uint64::new(600)),
+                    ..ConfigUpdate::default()
+                },
+            );
+            let block_time = Uint64::new(mock_env().block.time.seconds());
+            let relay_msg = |resolve_time: Uint64| Relay {
+                symbol_rates: vec![(String::from("AAA"), Uint64::new(1000))],
+                resolve_time,
+                request_id: Uint64::one(),
+                strict: None,
+            };
+
+            // Test resolve times too far in the future are rejected
+            let msg = relay_msg(block_time + Uint64::new(61));
+            let err =
+                execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::ResolveTimeInFuture {
+                    resolve_time: block_time + Uint64::new(61),
+                    block_time,
+                    max_future_drift_seconds: Uint64::new(60),
+                }
+            );
+
+            // Test resolve times too far in the past are rejected, even when forced
+            let msg = ForceRelay {
+                symbol_rates: vec![(String::from("AAA"), Uint64::new(1000))],
+                resolve_time: block_time - Uint64::new(601),
+                request_id: Uint64::one(),
+                strict: None,
+            };
+            let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::ResolveTimeTooOld {
+                    resolve_time: block_time - Uint64::new(601),
+                    block_time,
+                    max_resolve_age_seconds: Uint64::new(600),
+                }
+            );
+
+            // Test resolve times within the bounds are accepted
+            for resolve_time in [block_time - Uint64::new(600), block_time + Uint64::new(60)] {
+                let msg = relay_msg(resolve_time);
+                execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap();
+            }
+        }
+
+        #[test]
+        fn relay_sets_summary_data() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relays(
+                deps.as_mut(),
+                "owner",
+                vec![String::from("relayer")],
+                vec![
+                    (String::from("AAA"), Uint64::new(1000)),
+                    (String::from("BBB"), Uint64::new(2000)),
+                ],
+                Uint64::new(100),
+                Uint64::one(),
+            );
+            update_config(
+                deps.as_mut(),
+                ConfigUpdate {
+                    max_deviation_bps: Some(1000),
+                    ..ConfigUpdate::default()
+                },
+            );
+
+            // Test updated, skipped and rejected symbols are reported
+            let msg = Relay {
+                symbol_rates: vec![
+                    (String::from("AAA"), Uint64::new(5000)),
+                    (String::from("CCC"), Uint64::new(3000)),
+                ],
+                resolve_time: Uint64::new(200),
+                request_id: Uint64::new(2),
+                strict: None,
+            };
+            let res = execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap();
+            let msg = Relay {
+                symbol_rates: vec![(String::from("BBB"), Uint64::new(2100))],
+                resolve_time: Uint64::new(50),
+                request_id: Uint64::new(3),
+                strict: None,
+            };
+            let outdated_res =
+                execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap();
+            assert_eq!(
+                from_binary::<RelaySummary>(&res.data.unwrap()).unwrap(),
+                RelaySummary {
+                    updated: vec![String::from("CCC")],
+                    rejected: vec![(String::from("AAA"), SkipReason::DeviationExceeded)],
+                    ..RelaySummary::default()
+                }
+            );
+            assert_eq!(
+                from_binary::<RelaySummary>(&outdated_res.data.unwrap()).unwrap(),
+                RelaySummary {
+                    skipped: vec![(String::from("BBB"), SkipReason::Outdated)],
+                    ..RelaySummary::default()
+                }
+            );
+
+            // Test ForceRelay reports the overridden symbols as updated
+            let msg = ForceRelay {
+                symbol_rates: vec![(String::from("AAA"), Uint64::new(5000))],
+                resolve_time: Uint64::new(200),
+                request_id: Uint64::new(2),
+                strict: None,
+            };
+            let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+            assert_eq!(
+                from_binary::<RelaySummary>(&res.data.unwrap()).unwrap(),
+                RelaySummary {
+                    updated: vec![String::from("AAA")],
+                    ..RelaySummary::default()
+                }
+            );
+        }
+
+        #[test]
+        fn strict_relay_fails_on_skipped_symbols() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relays(
+                deps.as_mut(),
+                "owner",
+                vec![String::from("relayer")],
+                vec![(String::from("AAA"), Uint64::new(1000))],
+                Uint64::new(100),
+                Uint64::one(),
+            );
+
+            // Test the whole relay is reverted when a symbol is skipped
+            let msg = Relay {
+                symbol_rates: vec![
+                    (String::from("BBB"), Uint64::new(2000)),
+                    (String::from("AAA"), Uint64::new(1100)),
+                ],
+                resolve_time: Uint64::new(100),
+                request_id: Uint64::new(2),
+                strict: Some(true),
+            };
+            let err =
+                execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::StrictRelayFailed {
+                    symbol: String::from("AAA"),
+                    reason: SkipReason::Outdated,
+                }
+            );
+
+            // Test strict relays succeed when every symbol is saved
+            let msg = Relay {
+                symbol_rates: vec![(String::from("AAA"), Uint64::new(1100))],
+                resolve_time: Uint64::new(200),
+                request_id: Uint64::new(2),
+                strict: Some(true),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap();
+        }
+
+        #[test]
+        fn relay_batch_applies_guard_per_group() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relays(
+                deps.as_mut(),
+                "owner",
+                vec![String::from("relayer")],
+                vec![(String::from("AAA"), Uint64::new(1000))],
+                Uint64::new(100),
+                Uint64::one(),
+            );
+
+            // Test every group is applied with its own resolve time and request ID
+            let msg = RelayBatch {
+                groups: vec![
+                    RelayGroup {
+                        symbol_rates: vec![(String::from("AAA"), Uint64::new(1100))],
+                        resolve_time: Uint64::new(90),
+                        request_id: Uint64::new(2),
+                    },
+                    RelayGroup {
+                        symbol_rates: vec![
+                            (String::from("AAA"), Uint64::new(1200)),
+                            (String::from("BBB"), Uint64::new(2000)),
+                        ],
+                        resolve_time: Uint64::new(200),
+                        request_id: Uint64::new(3),
+                    },
+                    RelayGroup {
+                        symbol_rates: vec![(String::from("CCC"), Uint64::new(3000))],
+                        resolve_time: Uint64::new(150),
+                        request_id: Uint64::new(4),
+                    },
+                ],
+                strict: None,
+            };
+            let res = execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap();
+            assert_eq!(
+                from_binary::<RelaySummary>(&res.data.unwrap()).unwrap(),
+                RelaySummary {
+                    updated: vec![
+                        String::from("AAA"),
+                        String::from("BBB"),
+                        String::from("CCC")
+                    ],
+                    skipped: vec![(String::from("AAA"), SkipReason::Outdated)],
+                    ..RelaySummary::default()
+                }
+            );
+            assert_eq!(
+                query_ref(deps.as_ref(), "AAA").unwrap(),
+                RefData::new(Uint64::new(1200), Uint64::new(200), Uint64::new(3))
+            );
+            assert_eq!(
+                query_ref(deps.as_ref(), "CCC").unwrap(),
+                RefData::new(Uint64::new(3000), Uint64::new(150), Uint64::new(4))
+            );
+            assert_eq!(
+                RELAYERS
+                    .load(deps.as_ref().storage, &Addr::unchecked("relayer"))
+                    .unwrap()
+                    .last_request_id,
+                Some(Uint64::new(4))
+            );
+        }
+    }
+
+    mod query {
+        use std::iter::zip;
+        use std::ops::Mul;
+
+        use cosmwasm_std::from_binary;
+
+        use crate::msg::QueryMsg::{GetRef, GetReferenceData, GetReferenceDataBulk};
+
+        use super::*;
+
+        #[test]
+        fn attempt_query_config() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup(deps.as_mut(), "owner");
+
+            // Test if query_config results are correct
+            assert!(ADMIN
+                .is_admin(deps.as_ref(), &Addr::unchecked("owner"))
+                .unwrap());
+        }
+
+        #[test]
+        fn attempt_query_is_relayer() {
+            let mut deps = mock_dependencies();
+            let relayer = String::from("relayer");
+            setup_relayers(deps.as_mut(), "owner", vec![relayer.clone()]);
+
+            // Test if is_relayer results are correct
+            assert!(query_is_relayer(deps.as_ref(), &Addr::unchecked(relayer.clone())).unwrap());
+            assert!(!query_is_relayer(deps.as_ref(), &Addr::unchecked("not_a_relayer")).unwrap());
+        }
+
+        #[test]
+        fn attempt_query_list_refs() {
+            // Setup
+            let mut deps = mock_dependencies();
+            let symbols = vec!["CCC", "AAA", "BBB"]
+                .into_iter()
+                .map(String::from)
+                .collect::<Vec<String>>();
+            let rates = [3000, 1000, 2000]
+                .iter()
+                .map(|r| Uint64::new(*r))
+                .collect::<Vec<Uint64>>();
+            setup_relays(
+                deps.as_mut(),
+                "owner",
+                vec![String::from("relayer")],
+                zip(symbols, rates).collect(),
+                Uint64::from(100u64),
+                Uint64::one(),
+            );
+
+            // Test if list_refs results are sorted by symbol and paginated
+            let msg = QueryMsg::ListRefs {
+                start_after: None,
+                limit: Some(2),
+            };
+            let binary_res = query(deps.as_ref(), mock_env(), msg).unwrap();
+            assert_eq!(
+                from_binary::<Vec<(String, RefData)>>(&binary_res).unwrap(),
+                vec![
+                    (
+                        String::from("AAA"),
+                        RefData::new(Uint64::new(1000), Uint64::from(100u64), Uint64::one())
+                    ),
+                    (
+                        String::from("BBB"),
+                        RefData::new(Uint64::new(2000), Uint64::from(100u64), Uint64::one())
+                    ),
+                ]
+            );
+
+            let msg = QueryMsg::ListRefs {
+                start_after: Some(String::from("BBB")),
+                limit: None,
+            };
+            let binary_res = query(deps.as_ref(), mock_env(), msg).unwrap();
+            assert_eq!(
+                from_binary::<Vec<(String, RefData)>>(&binary_res).unwrap(),
+                vec![(
+                    String::from("CCC"),
+                    RefData::new(Uint64::new(3000), Uint64::from(100u64), Uint64::one())
+                )]
+            );
+        }
+
+        #[test]
+        fn attempt_query_get_ref() {
+            // Setup
+            let mut deps = mock_dependencies();
+            let relayer = String::from("relayer");
+            let symbol = vec![String::from("AAA")];
+            let rate = vec![Uint64::new(1000)];
+            setup_relays(
+                deps.as_mut(),
+                "owner",
+                vec![relayer.clone()],
+                zip(symbol.clone(), rate.clone()).collect(),
+                Uint64::from(100u64),
+                Uint64::one(),
+            );
+
+            // Test if get_ref results are correct
+            let env = mock_env();
+            let msg = GetRef {
+                symbol: symbol[0].to_owned(),
+            };
+            let binary_res = query(deps.as_ref(), env, msg).unwrap();
+            assert_eq!(
+                from_binary::<RefData>(&binary_res).unwrap(),
+                RefData::new(rate[0], Uint64::from(100u64), Uint64::one())
+            );
+
+            // Test invalid symbol
+            let env = mock_env();
+            let msg = GetRef {
+                symbol: String::from("DNE"),
+            };
+            let err = query(deps.as_ref(), env, msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Std(StdError::not_found("std_reference::state::RefData"))
+            );
+        }
+
+        #[test]
+        fn attempt_query_get_reference_data() {
+            // Setup
+            let mut deps = mock_dependencies();
+            let relayer = String::from("relayer");
+            let symbol = vec![String::from("AAA")];
+            let rate = vec![Uint64::new(1000)];
+            setup_relays(
+                deps.as_mut(),
+                "owner",
+                vec![relayer.clone()],
+                zip(symbol.clone(), rate.clone()).collect(),
+                Uint64::from(100u64),
+                Uint64::one(),
+            );
+
+            // Test if get_reference_data results are correct
+            let env = mock_env();
+            let msg = GetReferenceData {
+                symbol_pair: (symbol[0].clone(), String::from("USD")),
+                max_age_seconds: None,
+            };
+            let binary_res = query(deps.as_ref(), env, msg).unwrap();
+            assert_eq!(
+                from_binary::<ReferenceData>(&binary_res).unwrap(),
+                ReferenceData::new(
+                    Uint256::from(rate[0]).mul(Uint256::from(E9)),
+                    Uint64::from(100u64),
+                    Uint64::MAX,
+                )
+            );
+
+            // Test invalid symbol
+            let env = mock_env();
+            let msg = GetReferenceData {
+                symbol_pair: (String::from("DNE"), String::from("USD")),
+                max_age_seconds: None,
+            };
+            let err = query(deps.as_ref(), env, msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Std(StdError::not_found("std_reference::state::RefData"))
+            );
+            // Test invalid symbols
+            let env = mock_env();
+            let msg = GetReferenceData {
+                symbol_pair: (String::from("DNE1"), String::from("DNE2")),
+                max_age_seconds: None,
+            };
+            let err = query(deps.as_ref(), env, msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Std(StdError::not_found("std_reference::state::RefData"))
+            );
+        }
+
+        #[test]
+        fn attempt_query_get_reference_data_bulk() {
+            // Setup
+            let mut deps = mock_dependencies();
+            let relayer = String::from("relayer");
+            let symbols = vec!["AAA", "BBB", "CCC"]
+                .into_iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<String>>();
+            let rates = [1000, 2000, 3000]
+                .iter()
+                .map(|r| Uint64::new(*r))
+                .collect::<Vec<Uint64>>();
+            setup_relays(
+                deps.as_mut(),
+                "owner",
+                vec![relayer.clone()],
+                zip(symbols.clone(), rates.clone()).collect(),
+                Uint64::from(100u64),
+                Uint64::one(),
+            );
+
+            // Test if get_reference_data results are correct
+            let env = mock_env();
+            let msg = GetReferenceDataBulk {
+                symbol_pairs: symbols
+                    .clone()
+                    .iter()
+                    .map(|s| (s.clone(), String::from("USD")))
+                    .collect::<Vec<(String, String)>>(),
+                max_age_seconds: None,
+            };
+            let binary_res = query(deps.as_ref(), env, msg).unwrap();
+            let expected_res = rates
+                .iter()
+                .map(|r| {
+                    ReferenceData::new(
+                        Uint256::from(*r).mul(Uint256::from(E9)),
+                        Uint64::from(100u64),
+                        Uint64::MAX,
+                    )
+                })
+                .collect::<Vec<ReferenceData>>();
+            assert_eq!(
+                from_binary::<Vec<ReferenceData>>(&binary_res).unwrap(),
+                expected_res
+            );
+
+            // Test invalid symbols
+            let env = mock_env();
+            let msg = GetReferenceDataBulk {
+                symbol_pairs: vec![
+                    (String::from("AAA"), String::from("USD")),
+                    (String::from("DNE1"), String::from("USD")),
+                    (String::from("DNE2"), String::from("USD")),
+                ],
+                max_age_seconds: None,
+            };
+            let err = query(deps.as_ref(), env, msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Std(StdError::not_found("std_reference::state::RefData"))
+            );
+
+            // Test invalid symbols
+            let env = mock_env();
+            let msg = GetReferenceDataBulk {
+                symbol_pairs: vec![
+                    (String::from("AAA"), String::from("DNE1")),
+                    (String::from("DNE2"), String::from("DNE2")),
+                    (String::from("BBB"), String::from("DNE1")),
+                ],
+                max_age_seconds: None,
+            };
+            let err = query(deps.as_ref(), env, msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Std(StdError::not_found("std_reference::state::RefData"))
+            );
+        }
+        #[test]
+        fn attempt_query_get_reference_data_with_max_age() {
+            // Setup
+            let mut deps = mock_dependencies();
+            let resolve_time = Uint64::new(mock_env().block.time.seconds() - 100);
+            setup_relays(
+                deps.as_mut(),
+                "owner",
+                vec![String::from("relayer")],
+                vec![(String::from("AAA"), Uint64::new(1000))],
+                resolve_time,
+                Uint64::one(),
+            );
+
+            // Test data within the max age
+            let env = mock_env();
+            let msg = GetReferenceData {
+                symbol_pair: (String::from("AAA"), String::from("USD")),
+                max_age_seconds: Some(Uint64::new(100)),
+            };
+            query(deps.as_ref(), env, msg).unwrap();
+
+            // Test data older than the max age
+            let env = mock_env();
+            let msg = GetReferenceData {
+                symbol_pair: (String::from("USD"), String::from("AAA")),
+                max_age_seconds: Some(Uint64::new(99)),
+            };
+            let err = query(deps.as_ref(), env, msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::StalePrice {
+                    symbol: String::from("AAA"),
+                    resolve_time,
+                    max_age_seconds: Uint64::new(99),
+                }
+            );
+        }
+
+        #[test]
+        fn attempt_query_get_reference_data_bulk_with_max_age() {
+            // Setup
+            let mut deps = mock_dependencies();
+            let now = mock_env().block.time.seconds();
+            setup_relays(
+                deps.as_mut(),
+                "owner",
+                vec![String::from("relayer")],
+                vec![(String::from("AAA"), Uint64::new(1000))],
+                Uint64::new(now - 100),
+                Uint64::one(),
+            );
+            let info = mock_info("relayer", &[]);
+            let msg = Relay {
+                symbol_rates: vec![(String::from("BBB"), Uint64::new(2000))],
+                resolve_time: Uint64::new(now - 10),
+                request_id: Uint64::new(2),
+                strict: None,
+            };
+            execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+            // Test fresh pairs
+            let env = mock_env();
+            let msg = GetReferenceDataBulk {
+                symbol_pairs: vec![(String::from("BBB"), String::from("USD"))],
+                max_age_seconds: Some(Uint64::new(10)),
+            };
+            query(deps.as_ref(), env, msg).unwrap();
+
+            // Test a stale pair within the bulk
+            let env = mock_env();
+            let msg = GetReferenceDataBulk {
+                symbol_pairs: vec![
+                    (String::from("BBB"), String::from("USD")),
+                    (String::from("BBB"), String::from("AAA")),
+                ],
+                max_age_seconds: Some(Uint64::new(50)),
+            };
+            let err = query(deps.as_ref(), env, msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::StalePrice {
+                    symbol: String::from("AAA"),
+                    resolve_time: Uint64::new(now - 100),
+                    max_age_seconds: Uint64::new(50),
+                }
+            );
+        }
+    }
+
+    mod history {
+        use cosmwasm_std::from_binary;
+
+        use crate::msg::ExecuteMsg::{ForceRelay, UpdateConfig};
+        use crate::msg::QueryMsg::GetRefHistory;
+
+        use super::*;
+
+        fn history(deps: Deps, start_after: Option<Uint64>, limit: Option<u32>) -> Vec<RefData> {
+            let msg = GetRefHistory {
+                symbol: String::from("AAA"),
+                start_after,
+                limit,
+            };
+            from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap()
+        }
+
+        #[test]
+        fn update_config_by_other() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup(deps.as_mut(), "owner");
+
+            // Test unauthorized attempt to update config
+            let info = mock_info("user", &[]);
+            let env = mock_env();
+            let msg = UpdateConfig(ConfigUpdate {
+                history_depth: Some(5),
+                ..Default::default()
+            });
+            let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Unauthorized {
+                    msg: String::from("Sender does not have the config_manager role")
+                }
+            );
+        }
+
+        #[test]
+        fn history_disabled_by_default() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relayers(deps.as_mut(), "owner", vec![String::from("relayer")]);
+            relay(deps.as_mut(), "relayer", vec![("AAA", 1000)], 100, 100).unwrap();
+
+            assert_eq!(history(deps.as_ref(), None, None), vec![]);
+        }
+
+        #[test]
+        fn relay_appends_and_prunes_history() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relayers(deps.as_mut(), "owner", vec![String::from("relayer")]);
+            set_history_depth(deps.as_mut(), 3);
+
+            for (rate, resolve_time) in [(1000, 10), (2000, 20), (3000, 30), (4000, 40)] {
+                relay(
+                    deps.as_mut(),
+                    "relayer",
+                    vec![("AAA", rate)],
+                    resolve_time,
+                    resolve_time,
+                )
+                .unwrap();
+            }
+
+            // Only the 3 newest entries are kept, newest first
+            assert_eq!(
+                history(deps.as_ref(), None, None),
+                vec![
+                    RefData::new(Uint64::new(4000), Uint64::new(40), Uint64::new(40)),
+                    RefData::new(Uint64::new(3000), Uint64::new(30), Uint64::new(30)),
+                    RefData::new(Uint64::new(2000), Uint64::new(20), Uint64::new(20)),
+                ]
+            );
+
+            // Test pagination
+            assert_eq!(
+                history(deps.as_ref(), Some(Uint64::new(40)), Some(1)),
+                vec![RefData::new(
+                    Uint64::new(3000),
+                    Uint64::new(30),
+                    Uint64::new(30)
+                )]
+            );
+
+            // Lowering the depth prunes on the next relay
+            set_history_depth(deps.as_mut(), 1);
+            relay(deps.as_mut(), "relayer", vec![("AAA", 5000)], 50, 50).unwrap();
+            assert_eq!(
+                history(deps.as_ref(), None, None),
+                vec![RefData::new(
+                    Uint64::new(5000),
+                    Uint64::new(50),
+                    Uint64::new(50)
+                )]
+            );
+        }
+
+        #[test]
+        fn force_relay_overwrites_history_entry() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relayers(deps.as_mut(), "owner", vec![String::from("relayer")]);
+            set_history_depth(deps.as_mut(), 3);
+            relay(deps.as_mut(), "relayer", vec![("AAA", 1000)], 10, 10).unwrap();
+            relay(deps.as_mut(), "relayer", vec![("AAA", 2000)], 20, 20).unwrap();
+            grant_role(deps.as_mut(), Role::ForceRelayer, "relayer");
+
+            // Force relay an entry with an existing resolve time
+            let info = mock_info("relayer", &[]);
+            let env = mock_env();
+            let msg = ForceRelay {
+                symbol_rates: vec![(String::from("AAA"), Uint64::new(1500))],
+                resolve_time: Uint64::new(10),
+                request_id: Uint64::new(11),
+                strict: None,
+            };
+            execute(deps.as_mut(), env, info, msg).unwrap();
+
+            assert_eq!(
+                history(deps.as_ref(), None, None),
+                vec![
+                    RefData::new(Uint64::new(2000), Uint64::new(20), Uint64::new(20)),
+                    RefData::new(Uint64::new(1500), Uint64::new(10), Uint64::new(11)),
+                ]
+            );
+            assert_eq!(
+                REF_HISTORY_COUNT
+                    .load(deps.as_ref().storage, "AAA")
+                    .unwrap(),
+                2
+            );
+        }
+    }
+
+    mod twap {
+        use std::ops::Mul;
+
+        use cosmwasm_std::from_binary;
+
+        use crate::msg::QueryMsg::GetTwap;
+
+        use super::*;
+
+        // Returns the resolve time the given number of seconds before the current block time
+        fn ago(seconds: u64) -> u64 {
+            mock_env().block.time.seconds() - seconds
+        }
+
+        fn setup_twap(mut deps: DepsMut) {
+            setup_relayers(deps.branch(), "owner", vec![String::from("relayer")]);
+            set_history_depth(deps, 10);
+        }
+
+        fn twap(
+            deps: Deps,
+            base: &str,
+            quote: &str,
+            window_seconds: u64,
+        ) -> Result<ReferenceData, ContractError> {
+            let msg = GetTwap {
+                symbol_pair: (base.to_string(), quote.to_string()),
+                window_seconds: Uint64::new(window_seconds),
+            };
+            query(deps, mock_env(), msg).map(|res| from_binary(&res).unwrap())
+        }
+
+        #[test]
+        fn attempt_query_twap() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_twap(deps.as_mut());
+            relay(deps.as_mut(), "relayer", vec![("AAA", 1000)], ago(200), 1).unwrap();
+            relay(deps.as_mut(), "relayer", vec![("AAA", 2000)], ago(100), 1).unwrap();
+            relay(deps.as_mut(), "relayer", vec![("AAA", 4000)], ago(50), 1).unwrap();
+
+            // Test TWAP weighted by how long each rate was in effect
+            let now = mock_env().block.time.seconds();
+            assert_eq!(
+                twap(deps.as_ref(), "AAA", "USD", 100).unwrap(),
+                ReferenceData::new(
+                    Uint256::from(3000u64).mul(Uint256::from(E9)),
+                    Uint64::new(now - 50),
+                    Uint64::MAX,
+                )
+            );
+            assert_eq!(
+                twap(deps.as_ref(), "AAA", "USD", 200).unwrap().rate,
+                Uint256::from(2000u64).mul(Uint256::from(E9))
+            );
+        }
+
+        #[test]
+        fn attempt_query_twap_of_pair() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_twap(deps.as_mut());
+            relay(
+                deps.as_mut(),
+                "relayer",
+                vec![("AAA", 1000), ("BBB", 1000)],
+                ago(100),
+                1,
+            )
+            .unwrap();
+            relay(deps.as_mut(), "relayer", vec![("AAA", 3000)], ago(75), 1).unwrap();
+            relay(deps.as_mut(), "relayer", vec![("BBB", 3000)], ago(50), 1).unwrap();
+
+            // AAA/BBB is 1 for 25s, 3 for 25s and 1 for 50s
+            assert_eq!(
+                twap(deps.as_ref(), "AAA", "BBB", 100).unwrap().rate,
+                Uint256::from(1_500_000_000_000_000_000u128)
+            );
+        }
+
+        #[test]
+        fn attempt_query_twap_without_enough_observations() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_twap(deps.as_mut());
+            relay(deps.as_mut(), "relayer", vec![("AAA", 1000)], ago(100), 1).unwrap();
+
+            // Test window longer than the stored history
+            assert_eq!(
+                twap(deps.as_ref(), "AAA", "USD", 101).unwrap_err(),
+                ContractError::Std(StdError::generic_err(
+                    "Not enough observations of AAA to cover the TWAP window"
+                ))
+            );
+
+            // Test zero window
+            assert_eq!(
+                twap(deps.as_ref(), "AAA", "USD", 0).unwrap_err(),
+                ContractError::Std(StdError::generic_err(
+                    "TWAP window must be greater than zero"
+                ))
+            );
+        }
+    }
+
+    mod circuit_breaker {
+        use cosmwasm_std::attr;
+
+        use crate::msg::ExecuteMsg::{ForceRelay, SetSymbolConfigs};
+
+        use super::*;
+
+        // This function will setup a relayer, a 10% default deviation limit and an initial rate
+        fn setup_circuit_breaker(mut deps: DepsMut) {
+            setup_relayers(deps.branch(), "owner", vec![String::from("relayer")]);
+            update_config(
+                deps.branch(),
+                ConfigUpdate {
+                    max_deviation_bps: Some(1000),
+                    ..Default::default()
+                },
+            );
+            relay(deps, "relayer", vec![("AAA", 1000)], 10, 1).unwrap();
+        }
+
+        #[test]
+        fn relay_exceeding_default_deviation() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_circuit_breaker(deps.as_mut());
+
+            // Test rate outside of the deviation limit
+            let res = relay(deps.as_mut(), "relayer", vec![("AAA", 1101)], 20, 1).unwrap();
+            assert!(res.attributes.contains(&attr("deviation_exceeded", "AAA")));
+            assert_eq!(
+                query_ref(deps.as_ref(), "AAA").unwrap(),
+                RefData::new(Uint64::new(1000), Uint64::new(10), Uint64::one())
+            );
+
+            // Test rate within the deviation limit
+            let res = relay(deps.as_mut(), "relayer", vec![("AAA", 900)], 30, 1).unwrap();
+            assert!(!res.attributes.contains(&attr("deviation_exceeded", "AAA")));
+            assert_eq!(
+                query_ref(deps.as_ref(), "AAA").unwrap().rate,
+                Uint64::new(900)
+            );
+        }
+
+        #[test]
+        fn relay_with_symbol_deviation() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_circuit_breaker(deps.as_mut());
+
+            // Test per-symbol deviation overriding the default
+            let msg = SetSymbolConfigs {
+                symbol_configs: vec![(
+                    String::from("AAA"),
+                    SymbolConfig {
+                        max_deviation_bps: Some(5000),
+                        ..SymbolConfig::default()
+                    },
+                )],
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+            relay(deps.as_mut(), "relayer", vec![("AAA", 1500)], 20, 1).unwrap();
+            assert_eq!(
+                query_ref(deps.as_ref(), "AAA").unwrap().rate,
+                Uint64::new(1500)
+            );
+        }
+
+        #[test]
+        fn force_relay_bypasses_deviation() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_circuit_breaker(deps.as_mut());
+            grant_role(deps.as_mut(), Role::ForceRelayer, "relayer");
+
+            // Test force relay with rate outside of the deviation limit
+            let msg = ForceRelay {
+                symbol_rates: vec![(String::from("AAA"), Uint64::new(1))],
+                resolve_time: Uint64::new(20),
+                request_id: Uint64::one(),
+                strict: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap();
+            assert_eq!(query_ref(deps.as_ref(), "AAA").unwrap().rate, Uint64::one());
+        }
+
+        #[test]
+        fn set_symbol_configs_by_other() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup(deps.as_mut(), "owner");
+
+            // Test unauthorized attempt to set symbol configs
+            let msg = SetSymbolConfigs {
+                symbol_configs: vec![(String::from("AAA"), SymbolConfig::default())],
+            };
+            let err = execute(deps.as_mut(), mock_env(), mock_info("user", &[]), msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Unauthorized {
+                    msg: String::from("Sender does not have the config_manager role")
+                }
+            );
+        }
+    }
+
+    mod quorum {
+        use cosmwasm_std::{attr, from_binary};
+
+        use crate::msg::QueryMsg::{GetRoundSubmissions, ListPendingRounds};
+
+        use super::*;
+
+        // This function will setup the relayers and the relay threshold
+        fn setup_quorum(mut deps: DepsMut, relay_threshold: u32) {
+            let relayers = vec!["relayer_1", "relayer_2", "relayer_3"]
+                .into_iter()
+                .map(|s| s.to_string())
+                .collect();
+            setup_relayers(deps.branch(), "owner", relayers);
+            update_config(
+                deps,
+                ConfigUpdate {
+                    relay_threshold: Some(relay_threshold),
+                    ..Default::default()
+                },
+            );
+        }
+
+        #[test]
+        fn relay_applies_median_after_threshold() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_quorum(deps.as_mut(), 3);
+
+            // Test submissions below the threshold are buffered
+            relay(
+                deps.as_mut(),
+                "relayer_1",
+                vec![("AAA", 100), ("BBB", 10)],
+                100,
+                1,
+            )
+            .unwrap();
+            relay(deps.as_mut(), "relayer_2", vec![("AAA", 300)], 100, 1).unwrap();
+            assert!(query_ref(deps.as_ref(), "AAA").is_err());
+
+            let msg = ListPendingRounds {
+                start_after: None,
+                limit: None,
+            };
+            let rounds: Vec<PendingRound> =
+                from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            assert_eq!(
+                rounds,
+                vec![PendingRound {
+                    request_id: Uint64::one(),
+                    resolve_time: Uint64::new(100),
+                    relayers: vec![Addr::unchecked("relayer_1"), Addr::unchecked("relayer_2")],
+                }]
+            );
+
+            let msg = GetRoundSubmissions {
+                request_id: Uint64::one(),
+                resolve_time: Uint64::new(100),
+            };
+            let submissions: Vec<(Addr, Vec<(String, Uint64)>)> =
+                from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            assert_eq!(
+                submissions,
+                vec![
+                    (
+                        Addr::unchecked("relayer_1"),
+                        vec![
+                            (String::from("AAA"), Uint64::new(100)),
+                            (String::from("BBB"), Uint64::new(10))
+                        ]
+                    ),
+                    (
+                        Addr::unchecked("relayer_2"),
+                        vec![(String::from("AAA"), Uint64::new(300))]
+                    ),
+                ]
+            );
+
+            // Test reaching the threshold applies the median
+            let res = relay(deps.as_mut(), "relayer_3", vec![("AAA", 200)], 100, 1).unwrap();
+            assert_eq!(
+                query_ref(deps.as_ref(), "AAA").unwrap(),
+                RefData::new(Uint64::new(200), Uint64::new(100), Uint64::one())
+            );
+
+            // Test the events are attributed to every relayer of the round
+            let relayers = res.events[0]
+                .attributes
+                .iter()
+                .find(|attr| attr.key == "relayers")
+                .unwrap();
+            assert_eq!(relayers.value, "relayer_1,relayer_2,relayer_3");
+
+            // Symbols without enough submissions are not applied and are reported as skipped
+            assert!(query_ref(deps.as_ref(), "BBB").is_err());
+            let summary: RelaySummary = from_binary(&res.data.unwrap()).unwrap();
+            assert_eq!(summary.updated, vec![String::from("AAA")]);
+            assert_eq!(
+                summary.skipped,
+                vec![(String::from("BBB"), SkipReason::InsufficientQuorum)]
+            );
+            assert_eq!(
+                res.events[1].attributes[2..],
+                [
+                    attr("symbol", "BBB"),
+                    attr("resolve_time", "100"),
+                    attr("request_id", "1"),
+                    attr("forced", "false"),
+                    attr("status", "skipped"),
+                    attr("reason", "insufficient_quorum"),
+                    attr("submissions", "1"),
+                ]
+            );
+            assert!(PENDING_ROUNDS.is_empty(deps.as_ref().storage));
+            assert!(ROUND_SUBMISSIONS.is_empty(deps.as_ref().storage));
+        }
+
+        #[test]
+        fn relay_applies_median_of_even_submissions() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_quorum(deps.as_mut(), 2);
+
+            relay(deps.as_mut(), "relayer_1", vec![("AAA", 100)], 100, 1).unwrap();
+            relay(deps.as_mut(), "relayer_2", vec![("AAA", 201)], 100, 1).unwrap();
+            assert_eq!(
+                query_ref(deps.as_ref(), "AAA").unwrap().rate,
+                Uint64::new(150)
+            );
+        }
+
+        #[test]
+        fn relay_twice_to_same_round() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_quorum(deps.as_mut(), 2);
+
+            // Test second submission by the same relayer
+            relay(deps.as_mut(), "relayer_1", vec![("AAA", 100)], 100, 1).unwrap();
+            let err = relay(deps.as_mut(), "relayer_1", vec![("AAA", 100)], 100, 1).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::AlreadySubmitted {
+                    relayer: Addr::unchecked("relayer_1"),
+                    request_id: Uint64::one(),
+                    resolve_time: Uint64::new(100),
+                }
+            );
+        }
+
+        #[test]
+        fn late_submission_does_not_open_round() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_quorum(deps.as_mut(), 2);
+            relay(deps.as_mut(), "relayer_1", vec![("AAA", 100)], 100, 1).unwrap();
+            relay(deps.as_mut(), "relayer_2", vec![("AAA", 300)], 100, 1).unwrap();
+
+            // Test a submission after the round finalized is skipped as outdated
+            let res = relay(deps.as_mut(), "relayer_3", vec![("AAA", 200)], 100, 1).unwrap();
+            let summary: RelaySummary = from_binary(&res.data.unwrap()).unwrap();
+            assert_eq!(
+                summary.skipped,
+                vec![(String::from("AAA"), SkipReason::Outdated)]
+            );
+            assert!(PENDING_ROUNDS.is_empty(deps.as_ref().storage));
+            assert!(ROUND_SUBMISSIONS.is_empty(deps.as_ref().storage));
+        }
+
+        #[test]
+        fn unreachable_threshold() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relayers(deps.as_mut(), "owner", vec![String::from("relayer_1")]);
+
+            // Test the threshold cannot exceed the number of relayers
+            let msg = ExecuteMsg::UpdateConfig(ConfigUpdate {
+                relay_threshold: Some(2),
+                ..ConfigUpdate::default()
+            });
+            let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::InvalidConfig {
+                    msg: String::from("Relay threshold 2 exceeds the number of relayers 1")
+                }
+            );
+        }
+
+        #[test]
+        fn removed_relayer_submissions_do_not_count() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_quorum(deps.as_mut(), 2);
+            relay(deps.as_mut(), "relayer_1", vec![("AAA", 100)], 100, 1).unwrap();
+
+            // Test the submissions of a removed relayer are dropped from the round
+            let msg = ExecuteMsg::RemoveRelayers {
+                relayers: vec![String::from("relayer_1")],
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+            assert!(PENDING_ROUNDS.is_empty(deps.as_ref().storage));
+            assert!(ROUND_SUBMISSIONS.is_empty(deps.as_ref().storage));
+
+            // Test the remaining relayers must reach the threshold on their own
+            relay(deps.as_mut(), "relayer_2", vec![("AAA", 300)], 100, 1).unwrap();
+            assert!(query_ref(deps.as_ref(), "AAA").is_err());
+            relay(deps.as_mut(), "relayer_3", vec![("AAA", 200)], 100, 1).unwrap();
+            assert_eq!(
+                query_ref(deps.as_ref(), "AAA").unwrap().rate,
+                Uint64::new(250)
+            );
+        }
+    }
+
+    mod admin {
+        use cosmwasm_std::from_binary;
+        use cw_controllers::AdminError;
+
+        use crate::msg::ExecuteMsg::{AcceptAdmin, CancelAdminProposal, ProposeAdmin};
+
+        use super::*;
+
+        fn propose_admin(
+            deps: DepsMut,
+            sender: &str,
+            expires_at: Option<Uint64>,
+        ) -> Result<Response, ContractError> {
+            let msg = ProposeAdmin {
+                admin: String::from("new_owner"),
+                expires_at,
+            };
+            execute(deps, mock_env(), mock_info(sender, &[]), msg)
+        }
+
+        fn accept_admin(deps: DepsMut, sender: &str) -> Result<Response, ContractError> {
+            execute(deps, mock_env(), mock_info(sender, &[]), AcceptAdmin {})
+        }
+
+        #[test]
+        fn propose_and_accept_admin() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup(deps.as_mut(), "owner");
+
+            // Test proposal does not change the admin
+            propose_admin(deps.as_mut(), "owner", None).unwrap();
+            assert!(ADMIN
+                .is_admin(deps.as_ref(), &Addr::unchecked("owner"))
+                .unwrap());
+            let pending_admin: Option<PendingAdmin> =
+                from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::PendingAdmin {}).unwrap())
+                    .unwrap();
+            assert_eq!(
+                pending_admin,
+                Some(PendingAdmin {
+                    address: Addr::unchecked("new_owner"),
+                    expires_at: None,
+                })
+            );
+
+            // Test accept by other
+            let err = accept_admin(deps.as_mut(), "user").unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Unauthorized {
+                    msg: String::from("Sender is not the pending admin")
+                }
+            );
+
+            // Test accept by proposed admin
+            accept_admin(deps.as_mut(), "new_owner").unwrap();
+            assert!(ADMIN
+                .is_admin(deps.as_ref(), &Addr::unchecked("new_owner"))
+                .unwrap());
+            assert_eq!(PENDING_ADMIN.may_load(deps.as_ref().storage).unwrap(), None);
+        }
+
+        #[test]
+        fn propose_admin_by_other() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup(deps.as_mut(), "owner");
+
+            // Test unauthorized attempt to propose admin
+            let err = propose_admin(deps.as_mut(), "user", None).unwrap_err();
+            assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
+        }
+
+        #[test]
+        fn accept_expired_admin_proposal() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup(deps.as_mut(), "owner");
+            let expires_at = Uint64::new(mock_env().block.time.seconds() - 1);
+            propose_admin(deps.as_mut(), "owner", Some(expires_at)).unwrap();
+
+            // Test accept after expiry
+            let err = accept_admin(deps.as_mut(), "new_owner").unwrap_err();
+            assert_eq!(err, ContractError::AdminProposalExpired { expires_at });
+        }
+
+        #[test]
+        fn cancel_admin_proposal() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup(deps.as_mut(), "owner");
+            propose_admin(deps.as_mut(), "owner", None).unwrap();
+
+            // Test cancel by other
+            let msg = CancelAdminProposal {};
+            let err = execute(deps.as_mut(), mock_env(), mock_info("user", &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
+
+            // Test cancel by admin
+            let msg = CancelAdminProposal {};
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+            let err = accept_admin(deps.as_mut(), "new_owner").unwrap_err();
+            assert_eq!(err, ContractError::NoPendingAdmin {});
+        }
+    }
+
+    mod roles {
+        use cosmwasm_std::from_binary;
+        use cw_controllers::AdminError;
+
+        use crate::msg::ExecuteMsg::{GrantRole, RevokeRole};
+        use crate::msg::QueryMsg::{HasRole, ListRoleMembers};
+
+        use super::*;
+
+        fn has_role(deps: Deps, role: Role, address: &str) -> bool {
+            let msg = HasRole {
+                role,
+                address: address.to_string(),
+            };
+            from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap()
+        }
+
+        #[test]
+        fn grant_and_revoke_role() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup(deps.as_mut(), "owner");
+
+            // Test granting a role
+            let msg = GrantRole {
+                role: Role::Pauser,
+                addresses: vec![String::from("pauser_2"), String::from("pauser_1")],
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+            assert!(has_role(deps.as_ref(), Role::Pauser, "pauser_1"));
+            assert!(!has_role(deps.as_ref(), Role::ConfigManager, "pauser_1"));
+
+            let msg = ListRoleMembers {
+                role: Role::Pauser,
+                start_after: None,
+                limit: None,
+            };
+            let members: Vec<Addr> =
+                from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            assert_eq!(
+                members,
+                vec![Addr::unchecked("pauser_1"), Addr::unchecked("pauser_2")]
+            );
+
+            // Test revoking a role
+            let msg = RevokeRole {
+                role: Role::Pauser,
+                addresses: vec![String::from("pauser_1")],
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+            assert!(!has_role(deps.as_ref(), Role::Pauser, "pauser_1"));
+            assert!(has_role(deps.as_ref(), Role::Pauser, "pauser_2"));
+        }
+
+        #[test]
+        fn grant_role_by_other() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup(deps.as_mut(), "owner");
+            grant_role(deps.as_mut(), Role::RelayerManager, "manager");
+
+            // Test role holders cannot grant roles
+            let msg = GrantRole {
+                role: Role::ForceRelayer,
+                addresses: vec![String::from("manager")],
+            };
+            let err =
+                execute(deps.as_mut(), mock_env(), mock_info("manager", &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
+        }
+
+        #[test]
+        fn add_relayers_by_relayer_manager() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup(deps.as_mut(), "owner");
+            grant_role(deps.as_mut(), Role::RelayerManager, "manager");
+
+            // Test relayer manager adding relayers
+            let msg = AddRelayers {
+                relayers: vec![String::from("relayer")],
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("manager", &[]), msg).unwrap();
+            assert!(query_is_relayer(deps.as_ref(), &Addr::unchecked("relayer")).unwrap());
+
+            // Test relayers cannot force relay without the role
+            let msg = ExecuteMsg::ForceRelay {
+                symbol_rates: vec![(String::from("AAA"), Uint64::new(1000))],
+                resolve_time: Uint64::new(100),
+                request_id: Uint64::one(),
+                strict: None,
+            };
+            let err =
+                execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Unauthorized {
+                    msg: String::from("Sender does not have the force_relayer role")
+                }
+            );
+        }
+    }
+
+    mod pause {
+        use cosmwasm_std::{attr, from_binary};
+
+        use crate::msg::ExecuteMsg::{Pause, Unpause};
+        use crate::msg::QueryMsg::{GetRef, IsPaused};
+
+        use super::*;
+
+        fn is_paused(deps: Deps, symbol: Option<&str>) -> bool {
+            let msg = IsPaused {
+                symbol: symbol.map(|s| s.to_string()),
+            };
+            from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap()
+        }
 
         #[test]
-        fn can_instantiate() {
+        fn pause_globally() {
+            // Setup
             let mut deps = mock_dependencies();
-            let init_msg = InstantiateMsg {};
-            let info = mock_info("owner", &[]);
-            let env = mock_env();
-            let res = instantiate(deps.as_mut(), env, info.clone(), init_msg).unwrap();
-            assert_eq!(0, res.messages.len());
-            assert_eq!(ADMIN.is_admin(deps.as_ref(), &info.sender).unwrap(), true);
+            setup_relayers(deps.as_mut(), "owner", vec![String::from("relayer")]);
+            relay(
+                deps.as_mut(),
+                "relayer",
+                vec![("AAA", 1000), ("BBB", 2000)],
+                100,
+                1,
+            )
+            .unwrap();
+            grant_role(deps.as_mut(), Role::Pauser, "pauser");
+
+            // Test global pause by pauser
+            let msg = Pause { symbols: None };
+            execute(deps.as_mut(), mock_env(), mock_info("pauser", &[]), msg).unwrap();
+            assert!(is_paused(deps.as_ref(), None));
+            assert!(is_paused(deps.as_ref(), Some("AAA")));
+            assert_eq!(
+                relay(
+                    deps.as_mut(),
+                    "relayer",
+                    vec![("AAA", 1000), ("BBB", 2000)],
+                    200,
+                    1
+                )
+                .unwrap_err(),
+                ContractError::Paused {}
+            );
+            let msg = GetRef {
+                symbol: String::from("AAA"),
+            };
+            assert_eq!(
+                query(deps.as_ref(), mock_env(), msg).unwrap_err(),
+                ContractError::FeedPaused {
+                    symbol: String::from("AAA")
+                }
+            );
+
+            // Test unpause
+            let msg = Unpause { symbols: None };
+            execute(deps.as_mut(), mock_env(), mock_info("pauser", &[]), msg).unwrap();
+            assert!(!is_paused(deps.as_ref(), None));
+            relay(
+                deps.as_mut(),
+                "relayer",
+                vec![("AAA", 1000), ("BBB", 2000)],
+                200,
+                1,
+            )
+            .unwrap();
+        }
+
+        #[test]
+        fn pause_symbol() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relayers(deps.as_mut(), "owner", vec![String::from("relayer")]);
+            relay(
+                deps.as_mut(),
+                "relayer",
+                vec![("AAA", 1000), ("BBB", 2000)],
+                100,
+                1,
+            )
+            .unwrap();
+
+            // Test pausing a single symbol
+            let msg = Pause {
+                symbols: Some(vec![String::from("AAA")]),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+            assert!(!is_paused(deps.as_ref(), None));
+            assert!(is_paused(deps.as_ref(), Some("AAA")));
+
+            // Only the paused symbol is skipped
+            let res = relay(
+                deps.as_mut(),
+                "relayer",
+                vec![("AAA", 1000), ("BBB", 2000)],
+                200,
+                1,
+            )
+            .unwrap();
+            assert!(res.attributes.contains(&attr("symbol_paused", "AAA")));
+            assert_eq!(
+                query_ref(deps.as_ref(), "BBB").unwrap().resolve_time,
+                Uint64::new(200)
+            );
+            assert_eq!(
+                query_ref(deps.as_ref(), "AAA").unwrap_err(),
+                ContractError::FeedPaused {
+                    symbol: String::from("AAA")
+                }
+            );
+            assert_eq!(
+                REFDATA
+                    .load(deps.as_ref().storage, "AAA")
+                    .unwrap()
+                    .resolve_time,
+                Uint64::new(100)
+            );
+        }
+
+        #[test]
+        fn pause_by_other() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup(deps.as_mut(), "owner");
+
+            // Test unauthorized attempt to pause
+            let msg = Pause { symbols: None };
+            let err = execute(deps.as_mut(), mock_env(), mock_info("user", &[]), msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Unauthorized {
+                    msg: String::from("Sender does not have the pauser role")
+                }
+            );
         }
     }
 
-    mod relay {
-        use std::iter::zip;
+    mod registry {
+        use cosmwasm_std::{attr, from_binary};
 
-        use cw_controllers::AdminError;
+        use crate::msg::ExecuteMsg::{DeregisterSymbols, RegisterSymbols};
+        use crate::msg::QueryMsg::ListSymbols;
+        use crate::state::Category;
+
+        use super::*;
+
+        fn symbol_info(display_name: &str, enabled: bool) -> SymbolInfo {
+            SymbolInfo {
+                display_name: display_name.to_string(),
+                category: Category::Crypto,
+                oracle_script_id: Uint64::new(360),
+                enabled,
+            }
+        }
+
+        // This function will register AAA as enabled and BBB as disabled
+        fn setup_registry(mut deps: DepsMut) {
+            setup_relayers(deps.branch(), "owner", vec![String::from("relayer")]);
+            let msg = RegisterSymbols {
+                symbols: vec![
+                    (String::from("AAA"), symbol_info("Asset A", true)),
+                    (String::from("BBB"), symbol_info("Asset B", false)),
+                ],
+            };
+            execute(deps, mock_env(), mock_info("owner", &[]), msg).unwrap();
+        }
+
+        #[test]
+        fn relay_skips_disabled_symbols() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_registry(deps.as_mut());
+
+            // Unregistered symbols are accepted unless required
+            let res = relay(
+                deps.as_mut(),
+                "relayer",
+                vec![("AAA", 1000), ("BBB", 2000), ("CCC", 3000)],
+                100,
+                1,
+            )
+            .unwrap();
+            assert!(res.attributes.contains(&attr("symbol_disabled", "BBB")));
+            assert!(query_ref(deps.as_ref(), "AAA").is_ok());
+            assert!(query_ref(deps.as_ref(), "BBB").is_err());
+            assert!(query_ref(deps.as_ref(), "CCC").is_ok());
+        }
+
+        #[test]
+        fn relay_skips_unregistered_symbols_when_required() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_registry(deps.as_mut());
+            update_config(
+                deps.as_mut(),
+                ConfigUpdate {
+                    require_registered_symbols: Some(true),
+                    ..Default::default()
+                },
+            );
+
+            // Test unregistered symbol is skipped
+            let res = relay(
+                deps.as_mut(),
+                "relayer",
+                vec![("AAA", 1000), ("BBB", 2000), ("CCC", 3000)],
+                100,
+                1,
+            )
+            .unwrap();
+            assert!(res.attributes.contains(&attr("symbol_unregistered", "CCC")));
+            assert!(query_ref(deps.as_ref(), "AAA").is_ok());
+            assert!(query_ref(deps.as_ref(), "CCC").is_err());
+        }
+
+        #[test]
+        fn attempt_query_list_symbols() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_registry(deps.as_mut());
+            relay(
+                deps.as_mut(),
+                "relayer",
+                vec![("AAA", 1000), ("BBB", 2000), ("CCC", 3000)],
+                100,
+                1,
+            )
+            .unwrap();
+
+            // Test listing registered symbols with their latest RefData
+            let msg = ListSymbols {
+                start_after: None,
+                limit: None,
+            };
+            let symbols: Vec<SymbolEntry> =
+                from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            assert_eq!(
+                symbols,
+                vec![
+                    SymbolEntry {
+                        symbol: String::from("AAA"),
+                        info: symbol_info("Asset A", true),
+                        ref_data: Some(RefData::new(
+                            Uint64::new(1000),
+                            Uint64::new(100),
+                            Uint64::one()
+                        )),
+                    },
+                    SymbolEntry {
+                        symbol: String::from("BBB"),
+                        info: symbol_info("Asset B", false),
+                        ref_data: None,
+                    },
+                ]
+            );
+
+            // Test pagination and deregistration
+            let msg = DeregisterSymbols {
+                symbols: vec![String::from("BBB")],
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+            let msg = ListSymbols {
+                start_after: Some(String::from("AAA")),
+                limit: Some(1),
+            };
+            let symbols: Vec<SymbolEntry> =
+                from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            assert_eq!(symbols, vec![]);
+        }
+    }
+
+    mod aliases {
+        use cosmwasm_std::from_binary;
+
+        use crate::msg::ExecuteMsg::{RemoveAliases, SetAliases};
+        use crate::msg::QueryMsg::ListAliases;
+
+        use super::*;
+
+        #[test]
+        fn query_with_configured_anchor() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relays(
+                deps.as_mut(),
+                "owner",
+                vec![String::from("relayer")],
+                vec![(String::from("AAA"), Uint64::new(1000))],
+                Uint64::new(100),
+                Uint64::one(),
+            );
+            update_config(
+                deps.as_mut(),
+                ConfigUpdate {
+                    anchor: Some(String::from("EUR")),
+                    ..Default::default()
+                },
+            );
+
+            // Test the configured anchor replaces USD
+            assert_eq!(
+                query_ref(deps.as_ref(), "EUR").unwrap(),
+                RefData::new(E9, Uint64::MAX, Uint64::zero())
+            );
+            assert!(query_ref(deps.as_ref(), "USD").is_err());
+            assert_eq!(
+                query_reference_data(deps.as_ref(), &(String::from("AAA"), String::from("EUR")))
+                    .unwrap()
+                    .rate,
+                Uint256::from(1000u64) * Uint256::from(E9)
+            );
+        }
+
+        #[test]
+        fn query_with_aliases() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relays(
+                deps.as_mut(),
+                "owner",
+                vec![String::from("relayer")],
+                vec![(String::from("POL"), Uint64::new(500))],
+                Uint64::new(100),
+                Uint64::one(),
+            );
+            let msg = SetAliases {
+                aliases: vec![
+                    (String::from("MATIC"), String::from("POL")),
+                    (String::from("USDT.e"), String::from("USD")),
+                ],
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+            // Test aliases resolve before lookup
+            assert_eq!(
+                query_ref(deps.as_ref(), "MATIC").unwrap(),
+                query_ref(deps.as_ref(), "POL").unwrap()
+            );
+            assert_eq!(
+                query_ref(deps.as_ref(), "USDT.e").unwrap(),
+                RefData::new(E9, Uint64::MAX, Uint64::zero())
+            );
+
+            let msg = ListAliases {
+                start_after: None,
+                limit: None,
+            };
+            let aliases: Vec<(String, String)> =
+                from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            assert_eq!(
+                aliases,
+                vec![
+                    (String::from("MATIC"), String::from("POL")),
+                    (String::from("USDT.e"), String::from("USD")),
+                ]
+            );
+
+            // Test removed aliases no longer resolve
+            let msg = RemoveAliases {
+                aliases: vec![String::from("MATIC")],
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+            assert!(query_ref(deps.as_ref(), "MATIC").is_err());
+        }
+
+        #[test]
+        fn set_aliases_by_other() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup(deps.as_mut(), "owner");
+
+            // Test unauthorized attempt to set aliases
+            let msg = SetAliases {
+                aliases: vec![(String::from("MATIC"), String::from("POL"))],
+            };
+            let err = execute(deps.as_mut(), mock_env(), mock_info("user", &[]), msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Unauthorized {
+                    msg: String::from("Sender does not have the config_manager role")
+                }
+            );
+        }
+    }
+
+    mod relayers {
+        use cosmwasm_std::from_binary;
+
+        use crate::msg::ExecuteMsg::{
+            ConfirmRelayerKey, Relay, ResignRelayer, RotateRelayerKey, SetRelayerLabel,
+            SetRelayerPermissions,
+        };
+        use crate::state::Category;
+
+        use super::*;
+
+        #[test]
+        fn list_relayers() {
+            // Setup
+            let mut deps = mock_dependencies();
+            let relayers = vec!["relayer_1", "relayer_2", "relayer_3"]
+                .into_iter()
+                .map(String::from)
+                .collect::<Vec<String>>();
+            setup_relayers(deps.as_mut(), "owner", relayers);
+
+            // Test relayers are listed with their metadata
+            let msg = SetRelayerLabel {
+                relayer: String::from("relayer_2"),
+                label: Some(String::from("backup")),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+            let msg = QueryMsg::ListRelayers {
+                start_after: None,
+                limit: Some(2),
+            };
+            let res: Vec<(Addr, RelayerInfo)> =
+                from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            let added_at = Uint64::new(mock_env().block.time.seconds());
+            let mut labelled = RelayerInfo::new(added_at, Addr::unchecked("owner"));
+            labelled.label = Some(String::from("backup"));
+            assert_eq!(
+                res,
+                vec![
+                    (
+                        Addr::unchecked("relayer_1"),
+                        RelayerInfo::new(added_at, Addr::unchecked("owner"))
+                    ),
+                    (Addr::unchecked("relayer_2"), labelled),
+                ]
+            );
+
+            // Test pagination
+            let msg = QueryMsg::ListRelayers {
+                start_after: Some(String::from("relayer_2")),
+                limit: None,
+            };
+            let res: Vec<(Addr, RelayerInfo)> =
+                from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            assert_eq!(
+                res.into_iter().map(|(a, _)| a).collect::<Vec<Addr>>(),
+                vec![Addr::unchecked("relayer_3")]
+            );
+        }
+
+        #[test]
+        fn relay_updates_relayer_info() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relayers(deps.as_mut(), "owner", vec![String::from("relayer")]);
+
+            // Test relay statistics are recorded
+            let mut env = mock_env();
+            for request_id in 1..=2 {
+                env.block.time = env.block.time.plus_seconds(10);
+                let msg = Relay {
+                    symbol_rates: vec![(String::from("AAA"), Uint64::new(1000))],
+                    resolve_time: Uint64::new(100 + request_id),
+                    request_id: Uint64::new(request_id),
+                    strict: None,
+                };
+                execute(deps.as_mut(), env.clone(), mock_info("relayer", &[]), msg).unwrap();
+            }
+            let info = RELAYERS
+                .load(deps.as_ref().storage, &Addr::unchecked("relayer"))
+                .unwrap();
+            assert_eq!(info.relay_count, Uint64::new(2));
+            assert_eq!(info.last_request_id, Some(Uint64::new(2)));
+            assert_eq!(
+                info.last_relay_time,
+                Some(Uint64::new(env.block.time.seconds()))
+            );
+        }
+
+        #[test]
+        fn relay_restricted_to_permitted_symbols() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relayers(deps.as_mut(), "owner", vec![String::from("relayer")]);
+            let msg = ExecuteMsg::RegisterSymbols {
+                symbols: vec![(
+                    String::from("EUR"),
+                    SymbolInfo {
+                        display_name: String::from("Euro"),
+                        category: Category::Forex,
+                        oracle_script_id: Uint64::new(360),
+                        enabled: true,
+                    },
+                )],
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+            let permissions = RelayerPermissions {
+                symbols: vec![String::from("BTC")],
+                categories: vec![Category::Forex],
+            };
+            let msg = SetRelayerPermissions {
+                relayer: String::from("relayer"),
+                permissions: Some(permissions.clone()),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+            let relay_msg = |symbol: &str| Relay {
+                symbol_rates: vec![(symbol.to_string(), Uint64::new(1000))],
+                resolve_time: Uint64::new(100),
+                request_id: Uint64::one(),
+                strict: None,
+            };
+
+            // Test permitted symbols and categories are accepted
+            for symbol in ["BTC", "EUR"] {
+                execute(
+                    deps.as_mut(),
+                    mock_env(),
+                    mock_info("relayer", &[]),
+                    relay_msg(symbol),
+                )
+                .unwrap();
+            }
+
+            // Test other symbols are rejected
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("relayer", &[]),
+                relay_msg("ETH"),
+            )
+            .unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::UnauthorizedSymbol {
+                    relayer: Addr::unchecked("relayer"),
+                    symbol: String::from("ETH"),
+                }
+            );
+
+            // Test permissions are listed and can be lifted
+            let msg = QueryMsg::ListRelayerPermissions {
+                start_after: None,
+                limit: None,
+            };
+            let res: Vec<(Addr, RelayerPermissions)> =
+                from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            assert_eq!(res, vec![(Addr::unchecked("relayer"), permissions)]);
+            let msg = SetRelayerPermissions {
+                relayer: String::from("relayer"),
+                permissions: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("relayer", &[]),
+                relay_msg("ETH"),
+            )
+            .unwrap();
+        }
+
+        #[test]
+        fn force_relay_restricted_to_permitted_symbols() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup(deps.as_mut(), "owner");
+            grant_role(deps.as_mut(), Role::ForceRelayer, "force_relayer");
+            let msg = SetRelayerPermissions {
+                relayer: String::from("force_relayer"),
+                permissions: Some(RelayerPermissions {
+                    symbols: vec![String::from("BTC")],
+                    ..RelayerPermissions::default()
+                }),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+            // Test unauthorized symbols are rejected
+            let msg = ExecuteMsg::ForceRelay {
+                symbol_rates: vec![(String::from("ETH"), Uint64::new(1000))],
+                resolve_time: Uint64::new(100),
+                request_id: Uint64::one(),
+                strict: None,
+            };
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("force_relayer", &[]),
+                msg,
+            )
+            .unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::UnauthorizedSymbol {
+                    relayer: Addr::unchecked("force_relayer"),
+                    symbol: String::from("ETH"),
+                }
+            );
+        }
+
+        #[test]
+        fn rotate_relayer_key() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relayers(deps.as_mut(), "owner", vec![String::from("relayer")]);
+            let msg = SetRelayerPermissions {
+                relayer: String::from("relayer"),
+                permissions: Some(RelayerPermissions {
+                    symbols: vec![String::from("BTC")],
+                    ..RelayerPermissions::default()
+                }),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+            grant_role(deps.as_mut(), Role::ForceRelayer, "relayer");
+            grant_role(deps.as_mut(), Role::ConfigManager, "relayer");
+            let info = RELAYERS
+                .load(deps.as_ref().storage, &Addr::unchecked("relayer"))
+                .unwrap();
+
+            // Test the metadata, permissions and force relayer role move to the new address at once
+            let msg = RotateRelayerKey {
+                new_address: String::from("new_relayer"),
+                require_confirmation: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap();
+            assert_eq!(
+                is_relayers(
+                    deps.as_ref(),
+                    vec![Addr::unchecked("relayer"), Addr::unchecked("new_relayer")]
+                ),
+                [false, true]
+            );
+            assert_eq!(
+                RELAYERS
+                    .load(deps.as_ref().storage, &Addr::unchecked("new_relayer"))
+                    .unwrap(),
+                info
+            );
+            assert!(RELAYER_PERMISSIONS.has(deps.as_ref().storage, &Addr::unchecked("new_relayer")));
+            assert!(!RELAYER_PERMISSIONS.has(deps.as_ref().storage, &Addr::unchecked("relayer")));
+            assert!(assert_role(
+                deps.as_ref(),
+                &Addr::unchecked("new_relayer"),
+                Role::ForceRelayer
+            )
+            .is_ok());
+            assert!(assert_role(
+                deps.as_ref(),
+                &Addr::unchecked("relayer"),
+                Role::ForceRelayer
+            )
+            .is_err());
 
-        use crate::msg::ExecuteMsg::{AddRelayers, ForceRelay, Relay, RemoveRelayers};
+            // Test other roles stay with the old address and are left to GrantRole and RevokeRole
+            assert!(ROLES.has(
+                deps.as_ref().storage,
+                (Role::ConfigManager.as_str(), &Addr::unchecked("relayer"))
+            ));
+            assert!(!ROLES.has(
+                deps.as_ref().storage,
+                (
+                    Role::ConfigManager.as_str(),
+                    &Addr::unchecked("new_relayer")
+                )
+            ));
 
-        use super::*;
+            // Test rotating onto an existing relayer is rejected
+            setup_relayers(deps.as_mut(), "owner", vec![String::from("other")]);
+            let msg = RotateRelayerKey {
+                new_address: String::from("other"),
+                require_confirmation: None,
+            };
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("new_relayer", &[]),
+                msg,
+            )
+            .unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::RelayerExists {
+                    relayer: Addr::unchecked("other")
+                }
+            );
+        }
 
         #[test]
-        fn add_relayers_by_owner() {
+        fn rotate_relayer_key_with_confirmation() {
             // Setup
             let mut deps = mock_dependencies();
-            let init_msg = InstantiateMsg {};
-            let info = mock_info("owner", &[]);
-            let env = mock_env();
-            instantiate(deps.as_mut(), env.clone(), info, init_msg).unwrap();
-            let relayers_to_add: Vec<String> = vec![
-                String::from("relayer_1"),
-                String::from("relayer_2"),
-                String::from("relayer_3"),
-            ];
+            setup_relayers(deps.as_mut(), "owner", vec![String::from("relayer")]);
 
-            // Test authorized attempt to add relayers
-            let info = mock_info("owner", &[]);
-            let env = mock_env();
-            let msg = AddRelayers {
-                relayers: relayers_to_add.clone(),
+            // Test the current key stays active until the new address confirms
+            let msg = RotateRelayerKey {
+                new_address: String::from("new_relayer"),
+                require_confirmation: Some(true),
             };
-            execute(deps.as_mut(), env, info, msg).unwrap();
+            execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap();
+            assert_eq!(
+                is_relayers(
+                    deps.as_ref(),
+                    vec![Addr::unchecked("relayer"), Addr::unchecked("new_relayer")]
+                ),
+                [true, false]
+            );
 
+            // Test only the pending new address can confirm
+            let msg = ConfirmRelayerKey {
+                old_address: String::from("relayer"),
+            };
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("user", &[]),
+                msg.clone(),
+            )
+            .unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::NoPendingRotation {
+                    relayer: Addr::unchecked("relayer")
+                }
+            );
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("new_relayer", &[]),
+                msg,
+            )
+            .unwrap();
             assert_eq!(
                 is_relayers(
                     deps.as_ref(),
-                    relayers_to_add
-                        .iter()
-                        .map(Addr::unchecked)
-                        .collect::<Vec<Addr>>(),
+                    vec![Addr::unchecked("relayer"), Addr::unchecked("new_relayer")]
                 ),
-                [true, true, true]
+                [false, true]
             );
+            assert!(!PENDING_ROTATIONS.has(deps.as_ref().storage, &Addr::unchecked("relayer")));
         }
 
         #[test]
-        fn add_relayers_by_other() {
+        fn rotated_key_cannot_rejoin_round() {
             // Setup
             let mut deps = mock_dependencies();
-            let init_msg = InstantiateMsg {};
-            let info = mock_info("owner", &[]);
-            let env = mock_env();
-            instantiate(deps.as_mut(), env.clone(), info, init_msg).unwrap();
+            let relayers = vec!["relayer", "relayer_2", "relayer_3"]
+                .into_iter()
+                .map(String::from)
+                .collect();
+            setup_relayers(deps.as_mut(), "owner", relayers);
+            update_config(
+                deps.as_mut(),
+                ConfigUpdate {
+                    relay_threshold: Some(3),
+                    ..ConfigUpdate::default()
+                },
+            );
+            let relay_msg = || Relay {
+                symbol_rates: vec![(String::from("BTC"), Uint64::new(1000))],
+                resolve_time: Uint64::new(100),
+                request_id: Uint64::one(),
+                strict: None,
+            };
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("relayer", &[]),
+                relay_msg(),
+            )
+            .unwrap();
 
-            // Test unauthorized attempt to add relayer
-            let info = mock_info("user", &[]);
-            let env = mock_env();
-            let msg = AddRelayers {
-                relayers: vec![String::from("relayer_1")],
+            // Test the round submission moves with the key
+            let msg = RotateRelayerKey {
+                new_address: String::from("new_relayer"),
+                require_confirmation: None,
             };
-            let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+            execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap();
+            let round = PENDING_ROUNDS
+                .load(deps.as_ref().storage, (1, 100))
+                .unwrap();
+            assert_eq!(round.relayers, vec![Addr::unchecked("new_relayer")]);
+            assert!(ROUND_SUBMISSIONS.has(
+                deps.as_ref().storage,
+                (1, 100, &Addr::unchecked("new_relayer"))
+            ));
+
+            // Test the new key cannot submit the same round again
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("new_relayer", &[]),
+                relay_msg(),
+            )
+            .unwrap_err();
             assert_eq!(
                 err,
-                ContractError::Admin {
-                    0: AdminError::NotAdmin {}
+                ContractError::AlreadySubmitted {
+                    relayer: Addr::unchecked("new_relayer"),
+                    request_id: Uint64::one(),
+                    resolve_time: Uint64::new(100),
                 }
             );
         }
 
         #[test]
-        fn remove_relayers_by_owner() {
+        fn resign_relayer() {
             // Setup
             let mut deps = mock_dependencies();
-            let relayers_list = vec![
-                String::from("relayer_1"),
-                String::from("relayer_2"),
-                String::from("relayer_3"),
-            ];
-            setup_relayers(deps.as_mut(), "owner", relayers_list.clone());
+            setup_relayers(deps.as_mut(), "owner", vec![String::from("relayer")]);
+            grant_role(deps.as_mut(), Role::ForceRelayer, "relayer");
+            grant_role(deps.as_mut(), Role::Pauser, "relayer");
 
-            // Remove relayer
-            let relayers_to_remove = relayers_list[..2].to_vec();
-            let info = mock_info("owner", &[]);
-            let env = mock_env();
-            let msg = RemoveRelayers {
-                relayers: relayers_to_remove,
-            };
-            execute(deps.as_mut(), env, info, msg).unwrap();
+            // Test relayers can remove themselves together with their force relayer role
+            let msg = ResignRelayer {};
+            execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap();
+            assert!(!query_is_relayer(deps.as_ref(), &Addr::unchecked("relayer")).unwrap());
+            assert!(assert_role(
+                deps.as_ref(),
+                &Addr::unchecked("relayer"),
+                Role::ForceRelayer
+            )
+            .is_err());
+            assert!(assert_role(deps.as_ref(), &Addr::unchecked("relayer"), Role::Pauser).is_ok());
 
+            // Test non-relayers cannot resign
+            let msg = ResignRelayer {};
+            let err =
+                execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap_err();
             assert_eq!(
-                is_relayers(
-                    deps.as_ref(),
-                    relayers_list
-                        .into_iter()
-                        .map(Addr::unchecked)
-                        .collect::<Vec<Addr>>(),
-                ),
-                [false, false, true]
+                err,
+                ContractError::Unauthorized {
+                    msg: String::from("Sender is not a relayer")
+                }
             );
         }
 
         #[test]
-        fn remove_relayers_by_other() {
+        fn set_relayer_label_by_other() {
             // Setup
             let mut deps = mock_dependencies();
-            let relayers = vec![String::from("relayer_1")];
-            setup_relayers(deps.as_mut(), "owner", relayers.clone());
+            setup_relayers(deps.as_mut(), "owner", vec![String::from("relayer")]);
 
-            // Test unauthorized attempt to remove relayer
-            let info = mock_info("user", &[]);
-            let env = mock_env();
-            let msg = RemoveRelayers { relayers };
-            let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+            // Test unauthorized attempt to set a relayer label
+            let msg = SetRelayerLabel {
+                relayer: String::from("relayer"),
+                label: Some(String::from("label")),
+            };
+            let err = execute(deps.as_mut(), mock_env(), mock_info("user", &[]), msg).unwrap_err();
             assert_eq!(
                 err,
-                ContractError::Admin {
-                    0: AdminError::NotAdmin {}
+                ContractError::Unauthorized {
+                    msg: String::from("Sender does not have the relayer_manager role")
                 }
             );
         }
 
         #[test]
-        fn attempt_relay_by_relayer() {
+        fn migrate_legacy_relayers() {
             // Setup
             let mut deps = mock_dependencies();
-            let relayer = String::from("relayer");
-            setup_relayers(deps.as_mut(), "owner", vec![relayer.clone()]);
+            setup(deps.as_mut(), "owner");
+            set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "0.1.0").unwrap();
+            let legacy: Map<&Addr, bool> = Map::new("relayers");
+            legacy
+                .save(deps.as_mut().storage, &Addr::unchecked("relayer"), &true)
+                .unwrap();
 
-            // Test authorized attempt to relay data
-            let info = mock_info(relayer.as_str(), &[]);
-            let env = mock_env();
-            let symbols = vec!["AAA", "BBB", "CCC"]
-                .into_iter()
-                .map(|s| s.to_string())
-                .collect::<Vec<String>>();
-            let rates = [1000, 2000, 3000]
-                .iter()
-                .map(|r| Uint64::new(*r))
-                .collect::<Vec<Uint64>>();
+            // Test legacy relayers are converted to RelayerInfo
+            migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+            assert_eq!(
+                RELAYERS
+                    .load(deps.as_ref().storage, &Addr::unchecked("relayer"))
+                    .unwrap(),
+                RelayerInfo::new(
+                    Uint64::new(mock_env().block.time.seconds()),
+                    Addr::unchecked("owner")
+                )
+            );
+            assert_eq!(
+                get_contract_version(deps.as_ref().storage).unwrap().version,
+                CONTRACT_VERSION
+            );
+        }
+    }
+
+    mod request_ids {
+        use cosmwasm_std::from_binary;
+
+        use crate::msg::ExecuteMsg::{ForceRelay, Relay, RelayBatch};
+
+        use super::*;
 
+        #[test]
+        fn query_refs_by_request_id() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relayers(deps.as_mut(), "owner", vec![String::from("relayer")]);
+            set_history_depth(deps.as_mut(), 2);
+            relay(
+                deps.as_mut(),
+                "relayer",
+                vec![("AAA", 1000), ("BBB", 2000)],
+                100,
+                1,
+            )
+            .unwrap();
             let msg = Relay {
-                symbol_rates: zip(symbols.clone(), rates.clone())
-                    .collect::<Vec<(String, Uint64)>>(),
-                resolve_time: Uint64::from(100u64),
+                symbol_rates: vec![(String::from("AAA"), Uint64::new(1100))],
+                resolve_time: Uint64::new(200),
+                request_id: Uint64::new(2),
+                strict: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap();
+
+            // Test every symbol saved by a request is returned, even after being overwritten
+            let msg = QueryMsg::GetRefsByRequestId {
                 request_id: Uint64::one(),
+                start_after: None,
+                limit: None,
             };
-            execute(deps.as_mut(), env, info, msg).unwrap();
+            let res: Vec<(String, RefData)> =
+                from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            assert_eq!(
+                res,
+                vec![
+                    (
+                        String::from("AAA"),
+                        RefData::new(Uint64::new(1000), Uint64::new(100), Uint64::one())
+                    ),
+                    (
+                        String::from("BBB"),
+                        RefData::new(Uint64::new(2000), Uint64::new(100), Uint64::one())
+                    ),
+                ]
+            );
 
-            // Check if relay was successful
-            let reference_datas = query_reference_data_bulk(
-                deps.as_ref(),
-                &symbols
-                    .clone()
-                    .iter()
-                    .map(|s| (s.clone(), String::from("USD")))
-                    .collect::<Vec<(String, String)>>(),
+            // Test pagination
+            let msg = QueryMsg::GetRefsByRequestId {
+                request_id: Uint64::one(),
+                start_after: Some(String::from("AAA")),
+                limit: Some(1),
+            };
+            let res: Vec<(String, RefData)> =
+                from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            assert_eq!(
+                res.into_iter().map(|(s, _)| s).collect::<Vec<String>>(),
+                vec![String::from("BBB")]
+            );
+        }
+
+        #[test]
+        fn relay_rejects_reused_request_id() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relayers(deps.as_mut(), "owner", vec![String::from("relayer")]);
+            set_history_depth(deps.as_mut(), 2);
+            relay(deps.as_mut(), "relayer", vec![("AAA", 1000)], 100, 1).unwrap();
+            let relay_msg = |request_id: Uint64| Relay {
+                symbol_rates: vec![(String::from("BBB"), Uint64::new(2000))],
+                resolve_time: Uint64::new(200),
+                request_id,
+                strict: None,
+            };
+
+            // Test reused request IDs are accepted by default
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("relayer", &[]),
+                relay_msg(Uint64::one()),
             )
             .unwrap();
-            let retrieved_rates = reference_datas
-                .clone()
-                .into_iter()
-                .map(|rd| rd.rate / Uint256::from(E9))
-                .collect::<Vec<Uint256>>();
+
+            // Test reused request IDs are rejected once enabled, except through ForceRelay
+            update_config(
+                deps.as_mut(),
+                ConfigUpdate {
+                    reject_reused_request_ids: Some(true),
+                    ..ConfigUpdate::default()
+                },
+            );
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("relayer", &[]),
+                relay_msg(Uint64::one()),
+            )
+            .unwrap_err();
             assert_eq!(
-                retrieved_rates,
-                rates
-                    .iter()
-                    .map(|r| Uint256::from(*r))
-                    .collect::<Vec<Uint256>>()
+                err,
+                ContractError::RequestIdAlreadyApplied {
+                    request_id: Uint64::one()
+                }
+            );
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("relayer", &[]),
+                relay_msg(Uint64::new(2)),
+            )
+            .unwrap();
+            let msg = ForceRelay {
+                symbol_rates: vec![(String::from("CCC"), Uint64::new(3000))],
+                resolve_time: Uint64::new(200),
+                request_id: Uint64::one(),
+                strict: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+        }
+
+        #[test]
+        fn reject_reused_request_ids_requires_history() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup(deps.as_mut(), "owner");
+            let err = ContractError::InvalidConfig {
+                msg: String::from("reject_reused_request_ids requires a non-zero history_depth"),
+            };
+
+            // Test the rejection cannot be enabled without the history
+            let msg = ExecuteMsg::UpdateConfig(ConfigUpdate {
+                reject_reused_request_ids: Some(true),
+                ..ConfigUpdate::default()
+            });
+            let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+            assert_eq!(res.unwrap_err(), err);
+
+            // Test the history cannot be disabled while the rejection is enabled
+            update_config(
+                deps.as_mut(),
+                ConfigUpdate {
+                    history_depth: Some(2),
+                    reject_reused_request_ids: Some(true),
+                    ..ConfigUpdate::default()
+                },
+            );
+            let msg = ExecuteMsg::UpdateConfig(ConfigUpdate {
+                history_depth: Some(0),
+                ..ConfigUpdate::default()
+            });
+            let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+            assert_eq!(res.unwrap_err(), err);
+        }
+
+        #[test]
+        fn relay_batch_rejects_duplicate_request_ids() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relayers(deps.as_mut(), "owner", vec![String::from("relayer")]);
+            set_history_depth(deps.as_mut(), 2);
+            update_config(
+                deps.as_mut(),
+                ConfigUpdate {
+                    reject_reused_request_ids: Some(true),
+                    ..ConfigUpdate::default()
+                },
+            );
+            let group = |symbol: &str, resolve_time: u64| RelayGroup {
+                symbol_rates: vec![(String::from(symbol), Uint64::new(1000))],
+                resolve_time: Uint64::new(resolve_time),
+                request_id: Uint64::one(),
+            };
+
+            // Test groups of one batch cannot share a request ID
+            let msg = RelayBatch {
+                groups: vec![group("AAA", 100), group("BBB", 200)],
+                strict: None,
+            };
+            let err =
+                execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::RequestIdAlreadyApplied {
+                    request_id: Uint64::one()
+                }
+            );
+            assert!(REQUEST_REFS.is_empty(deps.as_ref().storage));
+        }
+
+        #[test]
+        fn request_index_is_pruned_with_history() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relayers(deps.as_mut(), "owner", vec![String::from("relayer")]);
+            set_history_depth(deps.as_mut(), 1);
+            relay(deps.as_mut(), "relayer", vec![("AAA", 1000)], 100, 1).unwrap();
+            relay(deps.as_mut(), "relayer", vec![("AAA", 1100)], 200, 2).unwrap();
+
+            // Test the request index only keeps RefData still in the history
+            assert!(!is_request_applied(deps.as_ref().storage, Uint64::one()));
+            assert!(is_request_applied(deps.as_ref().storage, Uint64::new(2)));
+
+            // Test nothing is indexed while the history is disabled
+            set_history_depth(deps.as_mut(), 0);
+            relay(deps.as_mut(), "relayer", vec![("AAA", 1200)], 300, 3).unwrap();
+            assert!(REQUEST_REFS.is_empty(deps.as_ref().storage));
+        }
+    }
+
+    mod compact {
+        use cosmwasm_std::from_binary;
+
+        use crate::msg::ExecuteMsg::{RelayCompact, SetSymbolIds};
+
+        use super::*;
+
+        // Packs (id, rate) pairs into RelayCompact records
+        fn pack(records: &[(u16, u64)]) -> Binary {
+            records
+                .iter()
+                .flat_map(|(id, rate)| [id.to_be_bytes().to_vec(), rate.to_be_bytes().to_vec()])
+                .flatten()
+                .collect::<Vec<u8>>()
+                .into()
+        }
+
+        fn setup_compact(mut deps: DepsMut) {
+            setup_relayers(deps.branch(), "owner", vec![String::from("relayer")]);
+            let msg = SetSymbolIds {
+                symbol_ids: vec![(1, String::from("AAA")), (2, String::from("BBB"))],
+            };
+            execute(deps, mock_env(), mock_info("owner", &[]), msg).unwrap();
+        }
+
+        #[test]
+        fn relay_compact() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_compact(deps.as_mut());
+
+            // Test compact records are relayed like Relay
+            let msg = RelayCompact {
+                data: pack(&[(1, 1000), (2, 2000)]),
+                resolve_time: Uint64::new(100),
+                request_id: Uint64::one(),
+                strict: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap();
+            assert_eq!(
+                query_ref(deps.as_ref(), "AAA").unwrap(),
+                RefData::new(Uint64::new(1000), Uint64::new(100), Uint64::one())
             );
+            assert_eq!(
+                query_ref(deps.as_ref(), "BBB").unwrap(),
+                RefData::new(Uint64::new(2000), Uint64::new(100), Uint64::one())
+            );
+
+            // Test symbol IDs are listed
+            let msg = QueryMsg::ListSymbolIds {
+                start_after: Some(1),
+                limit: None,
+            };
+            let res: Vec<(u16, String)> =
+                from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            assert_eq!(res, vec![(2, String::from("BBB"))]);
         }
 
         #[test]
-        fn attempt_relay_by_relayer_with_invalid_resolve_time() {
+        fn relay_compact_invalid_data() {
             // Setup
             let mut deps = mock_dependencies();
-            let relayer = String::from("relayer");
-            setup_relayers(deps.as_mut(), "owner", vec![relayer.clone()]);
+            setup_compact(deps.as_mut());
 
-            // Relay initial set of data
-            let info = mock_info(relayer.as_str(), &[]);
-            let env = mock_env();
-            let symbols = vec!["AAA", "BBB", "CCC"]
-                .into_iter()
-                .map(|s| s.to_string())
-                .collect::<Vec<String>>();
-            let rates = [1000, 2000, 3000]
-                .iter()
-                .map(|r| Uint64::new(*r))
-                .collect::<Vec<Uint64>>();
-            let msg = Relay {
-                symbol_rates: zip(symbols.clone(), rates.clone())
-                    .collect::<Vec<(String, Uint64)>>(),
-                resolve_time: Uint64::from(100u64),
+            // Test truncated records are rejected
+            let mut data = pack(&[(1, 1000)]).to_vec();
+            data.pop();
+            let msg = RelayCompact {
+                data: data.into(),
+                resolve_time: Uint64::new(100),
                 request_id: Uint64::one(),
+                strict: None,
             };
-            execute(deps.as_mut(), env, info, msg).unwrap();
+            let err =
+                execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::InvalidCompactData {
+                    len: 9,
+                    record_len: 10
+                }
+            );
 
-            // Test attempt to relay with invalid resolve times
-            let info = mock_info(relayer.as_str(), &[]);
-            let env = mock_env();
-            let old_rates = [1, 2, 3]
-                .iter()
-                .map(|r| Uint64::new(*r))
-                .collect::<Vec<Uint64>>();
-            let msg = Relay {
-                symbol_rates: zip(symbols.clone(), old_rates).collect::<Vec<(String, Uint64)>>(),
-                resolve_time: Uint64::from(90u64),
+            // Test unknown symbol IDs are rejected
+            let msg = RelayCompact {
+                data: pack(&[(1, 1000), (3, 3000)]),
+                resolve_time: Uint64::new(100),
                 request_id: Uint64::one(),
+                strict: None,
             };
-            execute(deps.as_mut(), env, info, msg).unwrap();
-
-            // Check if relay was successful
-            let reference_datas = query_reference_data_bulk(
-                deps.as_ref(),
-                &symbols
-                    .clone()
-                    .iter()
-                    .map(|s| (s.clone(), String::from("USD")))
-                    .collect::<Vec<(String, String)>>(),
-            )
-            .unwrap();
-            let retrieved_rates = reference_datas
-                .clone()
-                .into_iter()
-                .map(|rd| rd.rate / Uint256::from(E9))
-                .collect::<Vec<Uint256>>();
-            assert_eq!(
-                retrieved_rates,
-                rates
-                    .iter()
-                    .map(|r| Uint256::from(*r))
-                    .collect::<Vec<Uint256>>()
-            );
+            let err =
+                execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::UnknownSymbolId { id: 3 });
         }
 
         #[test]
-        fn attempt_relay_by_relayer_with_partially_invalid_resolve_time() {
+        fn relay_compact_by_other() {
             // Setup
             let mut deps = mock_dependencies();
-            let relayer = String::from("relayer");
-            setup_relayers(deps.as_mut(), "owner", vec![relayer.clone()]);
+            setup_compact(deps.as_mut());
 
-            // Relay initial set of data
-            let info = mock_info(relayer.as_str(), &[]);
-            let env = mock_env();
-            let symbols = vec!["AAA", "BBB", "CCC"]
-                .into_iter()
-                .map(|s| s.to_string())
-                .collect::<Vec<String>>();
-            let rates = [1000, 2000, 3000]
-                .iter()
-                .map(|r| Uint64::new(*r))
-                .collect::<Vec<Uint64>>();
-            let msg = Relay {
-                symbol_rates: zip(symbols.clone(), rates).collect::<Vec<(String, Uint64)>>(),
-                resolve_time: Uint64::from(10u64),
+            // Test non-relayers are rejected before the symbol IDs are resolved
+            let msg = RelayCompact {
+                data: pack(&[(3, 3000)]),
+                resolve_time: Uint64::new(100),
                 request_id: Uint64::one(),
+                strict: None,
             };
-            execute(deps.as_mut(), env, info, msg).unwrap();
-
-            // Only relay one symbol
-            let info = mock_info(relayer.as_str(), &[]);
-            let env = mock_env();
-            let msg = Relay {
-                symbol_rates: vec![(String::from("AAA"), Uint64::new(99999))],
-                resolve_time: Uint64::from(20u64),
-                request_id: Uint64::from(3u64),
-            };
-            execute(deps.as_mut(), env, info, msg).unwrap();
-
-            // Test attempt to relay with partially invalid resolve times
-            let info = mock_info(relayer.as_str(), &[]);
-            let env = mock_env();
-            let update_rates = [1, 2, 3]
-                .iter()
-                .map(|r| Uint64::new(*r))
-                .collect::<Vec<Uint64>>();
-            let msg = Relay {
-                symbol_rates: zip(symbols.clone(), update_rates).collect::<Vec<(String, Uint64)>>(),
-                resolve_time: Uint64::from(15u64),
-                request_id: Uint64::from(2u64),
-            };
-            execute(deps.as_mut(), env, info, msg).unwrap();
-
-            // Check if relay was successful
-            let reference_datas = query_reference_data_bulk(
-                deps.as_ref(),
-                &symbols
-                    .clone()
-                    .iter()
-                    .map(|s| (s.clone(), String::from("USD")))
-                    .collect::<Vec<(String, String)>>(),
-            )
-            .unwrap();
-            let retrieved_rates = reference_datas
-                .clone()
-                .into_iter()
-                .map(|rd| rd.rate / Uint256::from(E9))
-                .collect::<Vec<Uint256>>();
-            let expected_rates = vec![99999, 2, 3]
-                .iter()
-                .map(|r| Uint256::from(*r as u128))
-                .collect::<Vec<Uint256>>();
-            assert_eq!(retrieved_rates, expected_rates);
+            let err = execute(deps.as_mut(), mock_env(), mock_info("user", &[]), msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Unauthorized {
+                    msg: String::from("Sender is not a relayer")
+                }
+            );
         }
 
         #[test]
-        fn attempt_relay_by_others() {
+        fn set_symbol_ids_by_other() {
             // Setup
             let mut deps = mock_dependencies();
             setup(deps.as_mut(), "owner");
 
-            // Test unauthorized attempt to relay data
-            let info = mock_info("user", &[]);
-            let env = mock_env();
-            let symbols = vec!["AAA", "BBB", "CCC"]
-                .into_iter()
-                .map(|s| s.to_string())
-                .collect::<Vec<String>>();
-            let rates = [1000, 2000, 3000]
-                .iter()
-                .map(|r| Uint64::new(*r))
-                .collect::<Vec<Uint64>>();
-            let msg = Relay {
-                symbol_rates: zip(symbols.clone(), rates.clone())
-                    .collect::<Vec<(String, Uint64)>>(),
-                resolve_time: Uint64::zero(),
-                request_id: Uint64::zero(),
+            // Test unauthorized attempt to set symbol IDs
+            let msg = SetSymbolIds {
+                symbol_ids: vec![(1, String::from("AAA"))],
             };
-            let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+            let err = execute(deps.as_mut(), mock_env(), mock_info("user", &[]), msg).unwrap_err();
             assert_eq!(
                 err,
                 ContractError::Unauthorized {
-                    msg: String::from("Sender is not a relayer")
+                    msg: String::from("Sender does not have the config_manager role")
                 }
             );
         }
+    }
+
+    mod rate_limit {
+        use cosmwasm_std::{attr, from_binary};
+
+        use crate::msg::ExecuteMsg::{ForceRelay, SetSymbolConfigs};
+
+        use super::*;
 
         #[test]
-        fn attempt_force_relay_by_relayer() {
+        fn relay_skips_updates_within_min_interval() {
             // Setup
             let mut deps = mock_dependencies();
-            let relayer = String::from("relayer");
-            setup_relayers(deps.as_mut(), "owner", vec![relayer.clone()]);
-
-            // Test authorized attempt to relay data
-            let info = mock_info(relayer.as_str(), &[]);
-            let env = mock_env();
-            let symbols = vec!["AAA", "BBB", "CCC"]
-                .into_iter()
-                .map(|s| s.to_string())
-                .collect::<Vec<String>>();
-            let rates = [1000, 2000, 3000]
-                .iter()
-                .map(|r| Uint64::new(*r))
-                .collect::<Vec<Uint64>>();
-            let msg = ForceRelay {
-                symbol_rates: zip(symbols.clone(), rates.clone())
-                    .collect::<Vec<(String, Uint64)>>(),
-                resolve_time: Uint64::from(100u64),
-                request_id: Uint64::from(2u64),
+            setup_relays(
+                deps.as_mut(),
+                "owner",
+                vec![String::from("relayer")],
+                vec![
+                    (String::from("AAA"), Uint64::new(1000)),
+                    (String::from("BBB"), Uint64::new(1000)),
+                ],
+                Uint64::new(100),
+                Uint64::one(),
+            );
+            update_config(
+                deps.as_mut(),
+                ConfigUpdate {
+                    min_update_interval_seconds: Some(Uint64::new(60)),
+                    ..ConfigUpdate::default()
+                },
+            );
+            let msg = SetSymbolConfigs {
+                symbol_configs: vec![(
+                    String::from("BBB"),
+                    SymbolConfig {
+                        min_update_interval_seconds: Some(Uint64::new(10)),
+                        ..SymbolConfig::default()
+                    },
+                )],
             };
-            execute(deps.as_mut(), env, info, msg).unwrap();
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
 
-            // Test attempt to force relay
-            let info = mock_info(relayer.as_str(), &[]);
-            let env = mock_env();
-            let forced_rates = [1, 2, 3]
-                .iter()
-                .map(|r| Uint64::new(*r))
-                .collect::<Vec<Uint64>>();
+            // Test updates within the default interval are skipped and reported
+            let res = relay(deps.as_mut(), "relayer", vec![("AAA", 1000)], 159, 1).unwrap();
+            assert!(res.events[0]
+                .attributes
+                .contains(&attr("reason", "rate_limited")));
+            assert_eq!(
+                from_binary::<RelaySummary>(&res.data.unwrap()).unwrap(),
+                RelaySummary {
+                    skipped: vec![(String::from("AAA"), SkipReason::RateLimited)],
+                    ..RelaySummary::default()
+                }
+            );
+            let res = relay(deps.as_mut(), "relayer", vec![("AAA", 1000)], 160, 1).unwrap();
+            assert!(res.events[0]
+                .attributes
+                .contains(&attr("status", "updated")));
+
+            // Test the per-symbol interval overrides the default
+            let res = relay(deps.as_mut(), "relayer", vec![("BBB", 1000)], 110, 1).unwrap();
+            assert!(res.events[0]
+                .attributes
+                .contains(&attr("status", "updated")));
+        }
+
+        #[test]
+        fn force_relay_bypasses_min_interval() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_relays(
+                deps.as_mut(),
+                "owner",
+                vec![String::from("relayer")],
+                vec![(String::from("AAA"), Uint64::new(1000))],
+                Uint64::new(100),
+                Uint64::one(),
+            );
+            update_config(
+                deps.as_mut(),
+                ConfigUpdate {
+                    min_update_interval_seconds: Some(Uint64::new(60)),
+                    ..ConfigUpdate::default()
+                },
+            );
+
+            // Test ForceRelay updates within the interval
             let msg = ForceRelay {
-                symbol_rates: zip(symbols.clone(), forced_rates.clone())
-                    .collect::<Vec<(String, Uint64)>>(),
-                resolve_time: Uint64::from(90u64),
-                request_id: Uint64::one(),
+                symbol_rates: vec![(String::from("AAA"), Uint64::new(1100))],
+                resolve_time: Uint64::new(110),
+                request_id: Uint64::new(2),
+                strict: Some(true),
             };
-            execute(deps.as_mut(), env, info, msg).unwrap();
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+            assert_eq!(
+                query_ref(deps.as_ref(), "AAA").unwrap(),
+                RefData::new(Uint64::new(1100), Uint64::new(110), Uint64::new(2))
+            );
+        }
+    }
 
-            // Check if forced relay was successful
-            let reference_datas = query_reference_data_bulk(
-                deps.as_ref(),
-                &symbols
-                    .clone()
-                    .iter()
-                    .map(|s| (s.clone(), String::from("USD")))
-                    .collect::<Vec<(String, String)>>(),
+    mod timelock {
+        use cosmwasm_std::from_binary;
+        use cw_controllers::AdminError;
+
+        use crate::msg::ExecuteMsg::{CancelOperation, ExecuteOperation, ScheduleOperation};
+
+        use super::*;
+
+        fn setup_timelock(mut deps: DepsMut) {
+            setup(deps.branch(), "owner");
+            update_config(
+                deps,
+                ConfigUpdate {
+                    timelock_delay_seconds: Some(Uint64::new(3600)),
+                    ..ConfigUpdate::default()
+                },
+            );
+        }
+
+        #[test]
+        fn sensitive_messages_require_timelock() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_timelock(deps.as_mut());
+
+            // Test direct execution is rejected
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("owner", &[]),
+                add_relayer_msg(),
             )
-            .unwrap();
-            let retrieved_rates = reference_datas
-                .into_iter()
-                .map(|rd| rd.rate / Uint256::from(E9))
-                .collect::<Vec<Uint256>>();
+            .unwrap_err();
+            assert_eq!(err, ContractError::TimelockRequired {});
+
+            // Test the operation cannot be executed before its ETA
+            let msg = ScheduleOperation {
+                msg: Box::new(add_relayer_msg()),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+            let eta = Uint64::new(mock_env().block.time.seconds() + 3600);
+            let msg = ExecuteOperation { id: Uint64::zero() };
+            let err =
+                execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg).unwrap_err();
             assert_eq!(
-                retrieved_rates,
-                forced_rates
-                    .iter()
-                    .map(|r| Uint256::from(*r))
-                    .collect::<Vec<Uint256>>()
+                err,
+                ContractError::OperationNotReady {
+                    id: Uint64::zero(),
+                    eta
+                }
+            );
+
+            // Test anyone can execute the operation once ready
+            let mut env = mock_env();
+            env.block.time = env.block.time.plus_seconds(3600);
+            let msg = ExecuteOperation { id: Uint64::zero() };
+            execute(deps.as_mut(), env, mock_info("anyone", &[]), msg).unwrap();
+            assert!(query_is_relayer(deps.as_ref(), &Addr::unchecked("relayer")).unwrap());
+            assert!(TIMELOCK_OPERATIONS
+                .may_load(deps.as_ref().storage, 0)
+                .unwrap()
+                .is_none());
+        }
+
+        #[test]
+        fn lowering_delay_requires_timelock() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_timelock(deps.as_mut());
+
+            // Test raising the delay is immediate
+            update_config(
+                deps.as_mut(),
+                ConfigUpdate {
+                    timelock_delay_seconds: Some(Uint64::new(7200)),
+                    ..ConfigUpdate::default()
+                },
             );
+
+            // Test lowering the delay is rejected
+            let msg = ExecuteMsg::UpdateConfig(ConfigUpdate {
+                timelock_delay_seconds: Some(Uint64::zero()),
+                ..ConfigUpdate::default()
+            });
+            let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::TimelockRequired {});
         }
 
         #[test]
-        fn attempt_force_relay_by_other() {
+        fn update_delay_by_other() {
             // Setup
             let mut deps = mock_dependencies();
             setup(deps.as_mut(), "owner");
+            grant_role(deps.as_mut(), Role::ConfigManager, "config_manager");
 
-            // Test unauthorized attempt to relay data
-            let info = mock_info("user", &[]);
-            let env = mock_env();
-            let symbols = vec!["AAA", "BBB", "CCC"]
-                .into_iter()
-                .map(|s| s.to_string())
-                .collect::<Vec<String>>();
-            let rates = [1000, 2000, 3000]
-                .iter()
-                .map(|r| Uint64::new(*r))
-                .collect::<Vec<Uint64>>();
-            let msg = ForceRelay {
-                symbol_rates: zip(symbols.clone(), rates.clone())
-                    .collect::<Vec<(String, Uint64)>>(),
-                resolve_time: Uint64::zero(),
-                request_id: Uint64::zero(),
-            };
-            let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+            // Test config managers cannot change the delay
+            let msg = ExecuteMsg::UpdateConfig(ConfigUpdate {
+                timelock_delay_seconds: Some(Uint64::new(7200)),
+                ..ConfigUpdate::default()
+            });
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("config_manager", &[]),
+                msg,
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
+
+            // Test the delay is capped
+            let msg = ExecuteMsg::UpdateConfig(ConfigUpdate {
+                timelock_delay_seconds: Some(Uint64::MAX),
+                ..ConfigUpdate::default()
+            });
+            let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
             assert_eq!(
                 err,
-                ContractError::Unauthorized {
-                    msg: String::from("Sender is not a relayer")
+                ContractError::TimelockDelayTooLong {
+                    delay: Uint64::MAX,
+                    max: MAX_TIMELOCK_DELAY_SECONDS
                 }
             );
         }
-    }
 
-    mod query {
-        use std::iter::zip;
-        use std::ops::Mul;
+        #[test]
+        fn list_and_cancel_operations() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_timelock(deps.as_mut());
+            for _ in 0..2 {
+                let msg = ScheduleOperation {
+                    msg: Box::new(add_relayer_msg()),
+                };
+                execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+            }
+
+            // Test unauthorized attempt to schedule or cancel operations
+            let msg = ScheduleOperation {
+                msg: Box::new(add_relayer_msg()),
+            };
+            let err = execute(deps.as_mut(), mock_env(), mock_info("user", &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
+            let msg = CancelOperation { id: Uint64::zero() };
+            let err = execute(deps.as_mut(), mock_env(), mock_info("user", &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
+
+            // Test operations are listed until cancelled
+            let msg = CancelOperation { id: Uint64::zero() };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+            let msg = QueryMsg::ListOperations {
+                start_after: None,
+                limit: None,
+            };
+            let res: Vec<TimelockOperation> =
+                from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            assert_eq!(
+                res,
+                vec![TimelockOperation {
+                    id: Uint64::one(),
+                    msg: add_relayer_msg(),
+                    eta: Uint64::new(mock_env().block.time.seconds() + 3600),
+                    proposer: Addr::unchecked("owner"),
+                }]
+            );
+        }
+
+        #[test]
+        fn schedule_timelock_operation() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_timelock(deps.as_mut());
+
+            // Test timelock messages cannot be queued
+            let msg = ScheduleOperation {
+                msg: Box::new(ExecuteOperation { id: Uint64::zero() }),
+            };
+            let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::InvalidOperation {});
+        }
+    }
 
+    mod multisig {
         use cosmwasm_std::from_binary;
+        use cw_controllers::AdminError;
 
-        use crate::msg::QueryMsg::{GetRef, GetReferenceData, GetReferenceDataBulk};
+        use crate::msg::ExecuteMsg::{ConfigureMultisig, ExecuteProposal, Propose};
 
         use super::*;
 
+        fn setup_multisig(mut deps: DepsMut) {
+            setup(deps.branch(), "owner");
+            let msg = ConfigureMultisig {
+                signers: vec![
+                    (String::from("signer_1"), 1),
+                    (String::from("signer_2"), 1),
+                    (String::from("signer_3"), 2),
+                ],
+                threshold: 2,
+            };
+            execute(deps, mock_env(), mock_info("owner", &[]), msg).unwrap();
+        }
+
+        fn propose(deps: DepsMut, sender: &str, msg: ExecuteMsg) {
+            let msg = Propose {
+                msg: Box::new(msg),
+                expires_at: None,
+            };
+            execute(deps, mock_env(), mock_info(sender, &[]), msg).unwrap();
+        }
+
+        fn vote(deps: DepsMut, sender: &str, vote: Vote) -> Result<Response, ContractError> {
+            let msg = ExecuteMsg::Vote {
+                proposal_id: Uint64::zero(),
+                vote,
+            };
+            execute(deps, mock_env(), mock_info(sender, &[]), msg)
+        }
+
+        fn execute_proposal(deps: DepsMut, proposal_id: u64) -> Result<Response, ContractError> {
+            let msg = ExecuteProposal {
+                proposal_id: Uint64::new(proposal_id),
+            };
+            execute(deps, mock_env(), mock_info("anyone", &[]), msg)
+        }
+
         #[test]
-        fn attempt_query_config() {
+        fn configure_multisig_transfers_admin() {
             // Setup
             let mut deps = mock_dependencies();
-            setup(deps.as_mut(), "owner");
+            setup_multisig(deps.as_mut());
 
-            // Test if query_config results are correct
+            // Test the contract becomes the admin
+            assert!(ADMIN
+                .is_admin(deps.as_ref(), &mock_env().contract.address)
+                .unwrap());
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("owner", &[]),
+                add_relayer_msg(),
+            )
+            .unwrap_err();
             assert_eq!(
-                ADMIN
-                    .is_admin(deps.as_ref(), &Addr::unchecked("owner"))
-                    .unwrap(),
-                true
+                err,
+                ContractError::Unauthorized {
+                    msg: String::from("Sender does not have the relayer_manager role")
+                }
             );
         }
 
         #[test]
-        fn attempt_query_is_relayer() {
+        fn configure_multisig_clears_pending_admin() {
+            // Setup
             let mut deps = mock_dependencies();
-            let relayer = String::from("relayer");
-            setup_relayers(deps.as_mut(), "owner", vec![relayer.clone()]);
+            setup(deps.as_mut(), "owner");
+            let msg = ExecuteMsg::ProposeAdmin {
+                admin: String::from("new_admin"),
+                expires_at: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+            let msg = ConfigureMultisig {
+                signers: vec![(String::from("signer_1"), 1)],
+                threshold: 1,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
 
-            // Test if is_relayer results are correct
+            // Test a proposal made before the transfer can no longer be accepted
+            let msg = ExecuteMsg::AcceptAdmin {};
+            let err =
+                execute(deps.as_mut(), mock_env(), mock_info("new_admin", &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::NoPendingAdmin {});
+            assert!(ADMIN
+                .is_admin(deps.as_ref(), &mock_env().contract.address)
+                .unwrap());
+        }
+
+        #[test]
+        fn configure_invalid_multisig() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup(deps.as_mut(), "owner");
+
+            // Test thresholds above the total weight are rejected
+            let msg = ConfigureMultisig {
+                signers: vec![(String::from("signer_1"), 1)],
+                threshold: 2,
+            };
+            let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
             assert_eq!(
-                query_is_relayer(deps.as_ref(), &Addr::unchecked(relayer.clone())).unwrap(),
-                true
+                err,
+                ContractError::InvalidMultisig {
+                    msg: String::from("Threshold 2 must be between 1 and the total weight 1")
+                }
             );
+
+            // Test duplicate signers are rejected
+            let msg = ConfigureMultisig {
+                signers: vec![(String::from("signer_1"), 1), (String::from("signer_1"), 1)],
+                threshold: 1,
+            };
+            let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
             assert_eq!(
-                query_is_relayer(deps.as_ref(), &Addr::unchecked("not_a_relayer")).unwrap(),
-                false
+                err,
+                ContractError::InvalidMultisig {
+                    msg: String::from("Duplicate signer signer_1")
+                }
             );
+
+            // Test unauthorized attempt to configure a multisig
+            let msg = ConfigureMultisig {
+                signers: vec![(String::from("signer_1"), 1)],
+                threshold: 1,
+            };
+            let err = execute(deps.as_mut(), mock_env(), mock_info("user", &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
         }
 
         #[test]
-        fn attempt_query_get_ref() {
+        fn execute_passed_proposal() {
             // Setup
             let mut deps = mock_dependencies();
-            let relayer = String::from("relayer");
-            let symbol = vec![String::from("AAA")];
-            let rate = vec![Uint64::new(1000)];
-            setup_relays(
-                deps.as_mut(),
-                "owner",
-                vec![relayer.clone()],
-                zip(symbol.clone(), rate.clone()).collect(),
-                Uint64::from(100u64),
-                Uint64::one(),
+            setup_multisig(deps.as_mut());
+            propose(deps.as_mut(), "signer_1", add_relayer_msg());
+
+            // Test proposals below the threshold cannot be executed
+            vote(deps.as_mut(), "signer_2", Vote::No).unwrap();
+            let err = execute_proposal(deps.as_mut(), 0).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::ProposalNotPassed {
+                    proposal_id: Uint64::zero()
+                }
             );
 
-            // Test if get_ref results are correct
-            let env = mock_env();
-            let msg = GetRef {
-                symbol: symbol[0].to_owned(),
-            };
-            let binary_res = query(deps.as_ref(), env, msg).unwrap();
+            // Test proposals reaching the threshold are executed by anyone
+            vote(deps.as_mut(), "signer_3", Vote::Yes).unwrap();
+            execute_proposal(deps.as_mut(), 0).unwrap();
+            assert!(query_is_relayer(deps.as_ref(), &Addr::unchecked("relayer")).unwrap());
+
+            // Test executed proposals are closed
+            let err = execute_proposal(deps.as_mut(), 0).unwrap_err();
             assert_eq!(
-                from_binary::<RefData>(&binary_res).unwrap(),
-                RefData::new(rate[0], Uint64::from(100u64), Uint64::one())
+                err,
+                ContractError::ProposalClosed {
+                    proposal_id: Uint64::zero()
+                }
             );
 
-            // Test invalid symbol
-            let env = mock_env();
-            let msg = GetRef {
-                symbol: String::from("DNE"),
+            // Test votes are listed
+            let msg = QueryMsg::ListVotes {
+                proposal_id: Uint64::zero(),
+                start_after: None,
+                limit: None,
             };
-            let err = query(deps.as_ref(), env, msg).unwrap_err();
-            assert_eq!(err, StdError::not_found("std_reference::state::RefData"));
+            let res: Vec<(Addr, Vote)> =
+                from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            assert_eq!(
+                res,
+                vec![
+                    (Addr::unchecked("signer_1"), Vote::Yes),
+                    (Addr::unchecked("signer_2"), Vote::No),
+                    (Addr::unchecked("signer_3"), Vote::Yes),
+                ]
+            );
         }
 
         #[test]
-        fn attempt_query_get_reference_data() {
+        fn tally_uses_current_signers() {
             // Setup
             let mut deps = mock_dependencies();
-            let relayer = String::from("relayer");
-            let symbol = vec![String::from("AAA")];
-            let rate = vec![Uint64::new(1000)];
-            setup_relays(
+            setup_multisig(deps.as_mut());
+            propose(deps.as_mut(), "signer_1", add_relayer_msg());
+            vote(deps.as_mut(), "signer_2", Vote::Yes).unwrap();
+
+            // Test votes of removed signers no longer count
+            propose(
                 deps.as_mut(),
-                "owner",
-                vec![relayer.clone()],
-                zip(symbol.clone(), rate.clone()).collect(),
-                Uint64::from(100u64),
-                Uint64::one(),
+                "signer_3",
+                ConfigureMultisig {
+                    signers: vec![(String::from("signer_1"), 1), (String::from("signer_3"), 2)],
+                    threshold: 2,
+                },
             );
-
-            // Test if get_reference_data results are correct
-            let env = mock_env();
-            let msg = GetReferenceData {
-                symbol_pair: (symbol[0].clone(), String::from("USD")),
-            };
-            let binary_res = query(deps.as_ref(), env, msg).unwrap();
+            execute_proposal(deps.as_mut(), 1).unwrap();
+            let err = execute_proposal(deps.as_mut(), 0).unwrap_err();
             assert_eq!(
-                from_binary::<ReferenceData>(&binary_res).unwrap(),
-                ReferenceData::new(
-                    Uint256::from(rate[0]).mul(Uint256::from(E9)),
-                    Uint64::from(100u64),
-                    Uint64::MAX,
-                )
+                err,
+                ContractError::ProposalNotPassed {
+                    proposal_id: Uint64::zero()
+                }
+            );
+            let err = vote(deps.as_mut(), "signer_2", Vote::Yes).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Unauthorized {
+                    msg: String::from("Sender is not a multisig signer")
+                }
             );
+        }
 
-            // Test invalid symbol
-            let env = mock_env();
-            let msg = GetReferenceData {
-                symbol_pair: (String::from("DNE"), String::from("USD")),
-            };
-            let err = query(deps.as_ref(), env, msg).unwrap_err();
-            assert_eq!(err, StdError::not_found("std_reference::state::RefData"));
-            // Test invalid symbols
-            let env = mock_env();
-            let msg = GetReferenceData {
-                symbol_pair: (String::from("DNE1"), String::from("DNE2")),
-            };
-            let err = query(deps.as_ref(), env, msg).unwrap_err();
-            assert_eq!(err, StdError::not_found("std_reference::state::RefData"));
+        #[test]
+        fn vote_twice() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup_multisig(deps.as_mut());
+            propose(deps.as_mut(), "signer_1", add_relayer_msg());
+
+            // Test signers cannot vote twice
+            let err = vote(deps.as_mut(), "signer_1", Vote::No).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::AlreadyVoted {
+                    signer: Addr::unchecked("signer_1"),
+                    proposal_id: Uint64::zero()
+                }
+            );
         }
+    }
+
+    mod audit_log {
+        use cosmwasm_std::from_binary;
+
+        use crate::msg::ExecuteMsg::{ForceRelay, Relay, RemoveRelayers};
+
+        use super::*;
 
         #[test]
-        fn attempt_query_get_reference_data_bulk() {
+        fn admin_actions_are_logged() {
             // Setup
             let mut deps = mock_dependencies();
-            let relayer = String::from("relayer");
-            let symbols = vec!["AAA", "BBB", "CCC"]
-                .into_iter()
-                .map(|s| s.to_string())
-                .collect::<Vec<String>>();
-            let rates = [1000, 2000, 3000]
-                .iter()
-                .map(|r| Uint64::new(*r))
-                .collect::<Vec<Uint64>>();
-            setup_relays(
+            setup_relayers(
                 deps.as_mut(),
                 "owner",
-                vec![relayer.clone()],
-                zip(symbols.clone(), rates.clone()).collect(),
-                Uint64::from(100u64),
-                Uint64::one(),
+                vec![String::from("relayer_1"), String::from("relayer_2")],
             );
+            let msg = RemoveRelayers {
+                relayers: vec![String::from("relayer_2")],
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+            let msg = Relay {
+                symbol_rates: vec![(String::from("AAA"), Uint64::new(1000))],
+                resolve_time: Uint64::new(100),
+                request_id: Uint64::one(),
+                strict: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("relayer_1", &[]), msg).unwrap();
+            let msg = ForceRelay {
+                symbol_rates: vec![
+                    (String::from("AAA"), Uint64::new(900)),
+                    (String::from("BBB"), Uint64::new(2000)),
+                ],
+                resolve_time: Uint64::new(50),
+                request_id: Uint64::new(2),
+                strict: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
 
-            // Test if get_reference_data results are correct
+            // Test admin actions are logged in order, without relays
             let env = mock_env();
-            let msg = GetReferenceDataBulk {
-                symbol_pairs: symbols
-                    .clone()
-                    .iter()
-                    .map(|s| (s.clone(), String::from("USD")))
-                    .collect::<Vec<(String, String)>>(),
+            let entry = |seq: u64, action: &str, params: Vec<&str>| AuditEntry {
+                seq: Uint64::new(seq),
+                height: Uint64::new(env.block.height),
+                time: Uint64::new(env.block.time.seconds()),
+                actor: Addr::unchecked("owner"),
+                action: action.to_string(),
+                params: params.into_iter().map(String::from).collect(),
             };
-            let binary_res = query(deps.as_ref(), env, msg).unwrap();
-            let expected_res = rates
-                .iter()
-                .map(|r| {
-                    ReferenceData::new(
-                        Uint256::from(*r).mul(Uint256::from(E9)),
-                        Uint64::from(100u64),
-                        Uint64::MAX,
-                    )
-                })
-                .collect::<Vec<ReferenceData>>();
+            let msg = QueryMsg::GetAuditLog {
+                start_after: None,
+                limit: None,
+            };
+            let res: Vec<AuditEntry> =
+                from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
             assert_eq!(
-                from_binary::<Vec<ReferenceData>>(&binary_res).unwrap(),
-                expected_res
+                res,
+                vec![
+                    entry(0, "add_relayers", vec!["relayer_1", "relayer_2"]),
+                    entry(1, "remove_relayers", vec!["relayer_2"]),
+                    entry(2, "force_relay", vec!["AAA", "BBB"]),
+                ]
             );
 
-            // Test invalid symbols
-            let env = mock_env();
-            let msg = GetReferenceDataBulk {
-                symbol_pairs: vec![
-                    (String::from("AAA"), String::from("USD")),
-                    (String::from("DNE1"), String::from("USD")),
-                    (String::from("DNE2"), String::from("USD")),
-                ],
+            // Test pagination
+            let msg = QueryMsg::GetAuditLog {
+                start_after: Some(Uint64::zero()),
+                limit: Some(1),
             };
-            let err = query(deps.as_ref(), env, msg).unwrap_err();
-            assert_eq!(err, StdError::not_found("std_reference::state::RefData"));
+            let res: Vec<AuditEntry> =
+                from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            assert_eq!(res, vec![entry(1, "remove_relayers", vec!["relayer_2"])]);
+        }
 
-            // Test invalid symbols
-            let env = mock_env();
-            let msg = GetReferenceDataBulk {
-                symbol_pairs: vec![
-                    (String::from("AAA"), String::from("DNE1")),
-                    (String::from("DNE2"), String::from("DNE2")),
-                    (String::from("BBB"), String::from("DNE1")),
-                ],
+        #[test]
+        fn failed_actions_are_not_logged() {
+            // Setup
+            let mut deps = mock_dependencies();
+            setup(deps.as_mut(), "owner");
+
+            // Test rejected actions leave no entry
+            let msg = RemoveRelayers {
+                relayers: vec![String::from("relayer")],
             };
-            let err = query(deps.as_ref(), env, msg).unwrap_err();
-            assert_eq!(err, StdError::not_found("std_reference::state::RefData"));
+            execute(deps.as_mut(), mock_env(), mock_info("user", &[]), msg).unwrap_err();
+            assert!(query_audit_log(deps.as_ref(), None, None)
+                .unwrap()
+                .is_empty());
         }
     }
 }
@@ -1,7 +1,9 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Addr, DivideByZeroError, OverflowError, StdError, Uint64};
 use cw_controllers::AdminError;
 use thiserror::Error;
 
+use crate::state::SkipReason;
+
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
     #[error("{0}")]
@@ -10,6 +12,12 @@ pub enum ContractError {
     #[error("{0}")]
     Admin(#[from] AdminError),
 
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    DivideByZero(#[from] DivideByZeroError),
+
     #[error("Unauthorized: {msg}")]
     Unauthorized { msg: String },
 
@@ -18,4 +26,107 @@ pub enum ContractError {
 
     #[error("Cannot migrate from unsupported version: {previous_version}")]
     CannotMigrateVersion { previous_version: String },
+
+    #[error("Stale price: {symbol} was last updated at {resolve_time}, more than {max_age_seconds} seconds ago")]
+    StalePrice {
+        symbol: String,
+        resolve_time: Uint64,
+        max_age_seconds: Uint64,
+    },
+
+    #[error("Contract is paused")]
+    Paused {},
+
+    #[error("Feed paused: {symbol}")]
+    FeedPaused { symbol: String },
+
+    #[error("No pending admin proposal")]
+    NoPendingAdmin {},
+
+    #[error("Admin proposal expired at {expires_at}")]
+    AdminProposalExpired { expires_at: Uint64 },
+
+    #[error("Relayer {relayer} has already submitted round ({request_id}, {resolve_time})")]
+    AlreadySubmitted {
+        relayer: Addr,
+        request_id: Uint64,
+        resolve_time: Uint64,
+    },
+
+    #[error("Duplicate symbol: {symbol}")]
+    DuplicateSymbol { symbol: String },
+
+    #[error("Zero rate: {symbol}")]
+    ZeroRate { symbol: String },
+
+    #[error("Invalid symbol: {symbol}")]
+    InvalidSymbol { symbol: String },
+
+    #[error("Reserved symbol: {symbol}")]
+    ReservedSymbol { symbol: String },
+
+    #[error("Invalid config: {msg}")]
+    InvalidConfig { msg: String },
+
+    #[error("Too many symbols: {count} exceeds the maximum of {max}")]
+    TooManySymbols { count: u32, max: u32 },
+
+    #[error("Resolve time {resolve_time} is more than {max_future_drift_seconds} seconds ahead of block time {block_time}")]
+    ResolveTimeInFuture {
+        resolve_time: Uint64,
+        block_time: Uint64,
+        max_future_drift_seconds: Uint64,
+    },
+
+    #[error("Resolve time {resolve_time} is more than {max_resolve_age_seconds} seconds behind block time {block_time}")]
+    ResolveTimeTooOld {
+        resolve_time: Uint64,
+        block_time: Uint64,
+        max_resolve_age_seconds: Uint64,
+    },
+
+    #[error("Strict relay failed: {symbol} was not saved ({reason})")]
+    StrictRelayFailed { symbol: String, reason: SkipReason },
+
+    #[error("Request ID {request_id} has already been applied")]
+    RequestIdAlreadyApplied { request_id: Uint64 },
+
+    #[error("Invalid compact data: length {len} is not a multiple of {record_len}")]
+    InvalidCompactData { len: u32, record_len: u32 },
+
+    #[error("Unknown symbol ID: {id}")]
+    UnknownSymbolId { id: u16 },
+
+    #[error("Unauthorized symbol: {relayer} may not relay {symbol}")]
+    UnauthorizedSymbol { relayer: Addr, symbol: String },
+
+    #[error("Timelock required: the message must be queued with ScheduleOperation")]
+    TimelockRequired {},
+
+    #[error("Operation {id} cannot be executed before {eta}")]
+    OperationNotReady { id: Uint64, eta: Uint64 },
+
+    #[error("Timelock and multisig messages cannot be scheduled or proposed")]
+    InvalidOperation {},
+
+    #[error("Timelock delay {delay} exceeds the maximum of {max} seconds")]
+    TimelockDelayTooLong { delay: Uint64, max: Uint64 },
+
+    #[error("Invalid multisig: {msg}")]
+    InvalidMultisig { msg: String },
+
+    #[error("Signer {signer} has already voted on proposal {proposal_id}")]
+    AlreadyVoted { signer: Addr, proposal_id: Uint64 },
+
+    #[error("Proposal {proposal_id} is closed")]
+    ProposalClosed { proposal_id: Uint64 },
+
+    #[error("Proposal {proposal_id} has not reached the threshold")]
+    ProposalNotPassed { proposal_id: Uint64 },
+
+    #[error("Relayer already exists: {relayer}")]
+    RelayerExists { relayer: Addr },
+
+    #[error("No pending key rotation from {relayer} to the sender")]
+    NoPendingRotation { relayer: Addr },
 }
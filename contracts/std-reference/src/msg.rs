@@ -1,7 +1,11 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Uint64;
+use cosmwasm_std::{Addr, Binary, Uint64};
 
-use crate::state::{RefData, ReferenceData};
+use crate::state::{
+    AuditEntry, Config, Multisig, PendingAdmin, PendingRound, Proposal, RefData, ReferenceData,
+    RelayerInfo, RelayerPermissions, Role, SymbolConfig, SymbolEntry, SymbolInfo,
+    TimelockOperation, Vote,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {}
@@ -11,10 +15,40 @@ pub struct MigrateMsg {}
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    // Updates the contract config
-    UpdateAdmin {
+    // Proposes a new admin which takes over once it accepts the proposal
+    ProposeAdmin {
         // Address of the new owner
         admin: String,
+        // Unix time after which the proposal can no longer be accepted
+        expires_at: Option<Uint64>,
+    },
+    // Accepts the pending admin proposal, callable by the proposed admin
+    AcceptAdmin {},
+    // Cancels the pending admin proposal
+    CancelAdminProposal {},
+    // Pauses relaying and queries of the given symbols, or of every symbol if none are given
+    Pause {
+        // Symbols to pause
+        symbols: Option<Vec<String>>,
+    },
+    // Unpauses the given symbols, or lifts the global pause if none are given
+    Unpause {
+        // Symbols to unpause
+        symbols: Option<Vec<String>>,
+    },
+    // Grants a role to the given addresses
+    GrantRole {
+        // Role to grant
+        role: Role,
+        // Addresses to grant the role to
+        addresses: Vec<String>,
+    },
+    // Revokes a role from the given addresses
+    RevokeRole {
+        // Role to revoke
+        role: Role,
+        // Addresses to revoke the role from
+        addresses: Vec<String>,
     },
     // Whitelists addresses into relayer set
     AddRelayers {
@@ -26,6 +60,34 @@ pub enum ExecuteMsg {
         // Addresses to revoke the relayer rights
         relayers: Vec<String>,
     },
+    // Sets or clears the label of a relayer
+    SetRelayerLabel {
+        // Address of the relayer
+        relayer: String,
+        // Label of the relayer
+        label: Option<String>,
+    },
+    // Restricts the symbols a relayer may write, or lifts the restriction if no permissions are given
+    SetRelayerPermissions {
+        // Address of the relayer
+        relayer: String,
+        // Symbols and categories the relayer may write
+        permissions: Option<RelayerPermissions>,
+    },
+    // Moves the sender's relayer metadata, permissions and force relayer role to a new address
+    RotateRelayerKey {
+        // Address taking over the relayer
+        new_address: String,
+        // Whether the new address must confirm with ConfirmRelayerKey before the rotation applies
+        require_confirmation: Option<bool>,
+    },
+    // Confirms a pending relayer key rotation to the sender
+    ConfirmRelayerKey {
+        // Address of the relayer being rotated
+        old_address: String,
+    },
+    // Removes the sender from the relayers and revokes its force relayer role
+    ResignRelayer {},
     // Relays a vector of symbols and their corresponding rates
     Relay {
         // A vector of symbols and their corresponding rates where:
@@ -38,13 +100,165 @@ pub enum ExecuteMsg {
         resolve_time: Uint64,
         // Request ID of the results on BandChain
         request_id: Uint64,
+        // Fails the whole transaction if any symbol is not saved
+        strict: Option<bool>,
+    },
+    // Same as Relay but for groups of symbol rates from different requests
+    RelayBatch {
+        // Groups of symbol rates sharing a resolve time and request ID
+        groups: Vec<RelayGroup>,
+        // Fails the whole transaction if any symbol is not saved
+        strict: Option<bool>,
+    },
+    // Same as Relay but with symbols referenced by their registered numeric IDs
+    RelayCompact {
+        // Packed records of a big-endian u16 symbol ID followed by a big-endian u64 rate
+        data: Binary,
+        // Resolve time of request on BandChain in Unix timestamp
+        resolve_time: Uint64,
+        // Request ID of the results on BandChain
+        request_id: Uint64,
+        // Fails the whole transaction if any symbol is not saved
+        strict: Option<bool>,
     },
     // Same as Relay but without the resolve_time guard
     ForceRelay {
         symbol_rates: Vec<(String, Uint64)>,
         resolve_time: Uint64,
         request_id: Uint64,
+        strict: Option<bool>,
+    },
+    // Updates the contract configuration, fields left empty are unchanged
+    UpdateConfig(ConfigUpdate),
+    // Registers or updates symbols and their metadata
+    RegisterSymbols {
+        // A vector of symbols and their metadata
+        symbols: Vec<(String, SymbolInfo)>,
+    },
+    // Removes symbols from the registry
+    DeregisterSymbols {
+        // Symbols to remove
+        symbols: Vec<String>,
     },
+    // Sets aliases that resolve to other symbols when queried
+    SetAliases {
+        // A vector of aliases and the symbols they resolve to where:
+        // alias := (alias, symbol)
+        // e.g. <("USDT.e", "USDT"), ("MATIC", "POL")>
+        aliases: Vec<(String, String)>,
+    },
+    // Removes aliases
+    RemoveAliases {
+        // Aliases to remove
+        aliases: Vec<String>,
+    },
+    // Sets the per-symbol settings of the given symbols
+    SetSymbolConfigs {
+        // A vector of symbols and their settings
+        symbol_configs: Vec<(String, SymbolConfig)>,
+    },
+    // Removes the per-symbol settings of the given symbols
+    RemoveSymbolConfigs {
+        // Symbols to revert to the default settings
+        symbols: Vec<String>,
+    },
+    // Sets the numeric IDs used by RelayCompact to reference symbols
+    SetSymbolIds {
+        // A vector of IDs and the symbols they reference
+        symbol_ids: Vec<(u16, String)>,
+    },
+    // Removes numeric symbol IDs
+    RemoveSymbolIds {
+        // IDs to remove
+        ids: Vec<u16>,
+    },
+    // Queues an admin message that can be executed once the timelock delay has passed
+    ScheduleOperation {
+        // Message to execute
+        msg: Box<ExecuteMsg>,
+    },
+    // Executes a queued operation whose ETA has passed, callable by anyone
+    ExecuteOperation {
+        // ID of the operation
+        id: Uint64,
+    },
+    // Cancels a queued operation
+    CancelOperation {
+        // ID of the operation
+        id: Uint64,
+    },
+    // Transfers admin authority to the contract itself, governed by weighted signers,
+    // or updates the signers when executed through a proposal
+    ConfigureMultisig {
+        // Signers and their voting weights
+        signers: Vec<(String, u64)>,
+        // Total weight of yes votes required to execute a proposal
+        threshold: u64,
+    },
+    // Proposes an admin message to the multisig signers, counting as a yes vote of the proposer
+    Propose {
+        // Message to execute
+        msg: Box<ExecuteMsg>,
+        // Unix time after which the proposal expires
+        expires_at: Option<Uint64>,
+    },
+    // Votes on a multisig proposal
+    Vote {
+        // ID of the proposal
+        proposal_id: Uint64,
+        // Vote of the signer
+        vote: Vote,
+    },
+    // Executes a multisig proposal that reached the threshold, callable by anyone
+    ExecuteProposal {
+        // ID of the proposal
+        proposal_id: Uint64,
+    },
+    // Discards pending rounds that will not reach the relay threshold
+    RemovePendingRounds {
+        // Rounds to discard where round := (request_id, resolve_time)
+        rounds: Vec<(Uint64, Uint64)>,
+    },
+}
+
+#[cw_serde]
+pub struct RelayGroup {
+    // A vector of symbols and their corresponding rates
+    pub symbol_rates: Vec<(String, Uint64)>,
+    // Resolve time of request on BandChain in Unix timestamp
+    pub resolve_time: Uint64,
+    // Request ID of the results on BandChain
+    pub request_id: Uint64,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct ConfigUpdate {
+    // Number of RefData entries to keep per symbol in the history
+    pub history_depth: Option<u32>,
+    // Default maximum rate change in basis points accepted by Relay
+    pub max_deviation_bps: Option<u32>,
+    // Number of distinct relayers that must submit a round before it is applied, at most the
+    // number of relayers
+    pub relay_threshold: Option<u32>,
+    // Whether Relay skips symbols that are not in the symbol registry
+    pub require_registered_symbols: Option<bool>,
+    // Symbol that all rates are quoted against
+    pub anchor: Option<String>,
+    // Maximum number of symbols accepted in a single Relay or ForceRelay
+    pub max_symbols_per_relay: Option<u32>,
+    // Maximum number of seconds a resolve time may be ahead of the block time
+    pub max_future_drift_seconds: Option<Uint64>,
+    // Maximum number of seconds a resolve time may be behind the block time
+    pub max_resolve_age_seconds: Option<Uint64>,
+    // Whether Relay rejects request IDs of RefData still kept in the history, which requires a
+    // non-zero history depth
+    pub reject_reused_request_ids: Option<bool>,
+    // Default minimum number of seconds between accepted updates of a symbol
+    pub min_update_interval_seconds: Option<Uint64>,
+    // Number of seconds sensitive admin operations are queued before they can be executed,
+    // at most 30 days, only the admin may change it and lowering it requires the timelock
+    pub timelock_delay_seconds: Option<Uint64>,
 }
 
 #[cw_serde]
@@ -53,12 +267,61 @@ pub enum QueryMsg {
     // Returns admin accounts
     #[returns(cw_controllers::AdminResponse)]
     Admin {},
+    // Returns the pending admin proposal if there is one
+    #[returns(Option < PendingAdmin >)]
+    PendingAdmin {},
     // Queries if given a address is a relayer
     #[returns(bool)]
     IsRelayer {
         // Address to check relayer status
         relayer: String,
     },
+    // Returns the relayers and their metadata
+    #[returns(Vec < (Addr, RelayerInfo) >)]
+    ListRelayers {
+        // Only return relayers after this address
+        start_after: Option<String>,
+        // Maximum number of relayers to return
+        limit: Option<u32>,
+    },
+    // Returns the address a relayer is rotating to if the rotation awaits confirmation
+    #[returns(Option < Addr >)]
+    GetPendingRotation {
+        // Address of the relayer
+        relayer: String,
+    },
+    // Returns the relayers restricted to a set of symbols and their permissions
+    #[returns(Vec < (Addr, RelayerPermissions) >)]
+    ListRelayerPermissions {
+        // Only return relayers after this address
+        start_after: Option<String>,
+        // Maximum number of relayers to return
+        limit: Option<u32>,
+    },
+    // Queries if given a address has been granted a role
+    #[returns(bool)]
+    HasRole {
+        // Role to check
+        role: Role,
+        // Address to check
+        address: String,
+    },
+    // Returns the addresses granted a role
+    #[returns(Vec < Addr >)]
+    ListRoleMembers {
+        // Role to query
+        role: Role,
+        // Only return addresses after this address
+        start_after: Option<String>,
+        // Maximum number of addresses to return
+        limit: Option<u32>,
+    },
+    // Queries if a given symbol is paused, or if the contract is globally paused if none is given
+    #[returns(bool)]
+    IsPaused {
+        // Symbol to check
+        symbol: Option<String>,
+    },
     #[returns(RefData)]
     // Returns the RefData of a given symbol
     GetRef {
@@ -72,6 +335,9 @@ pub enum QueryMsg {
         // symbol_pair := (base_symbol, quote_symbol)
         // e.g. BTC/USD ≡ ("BTC", "USD")
         symbol_pair: (String, String),
+        // Maximum age in seconds of either asset's data relative to the current block time,
+        // older data returns a stale price error
+        max_age_seconds: Option<Uint64>,
     },
     #[returns(Vec < ReferenceData >)]
     // Returns the ReferenceDatas of the given asset pairings
@@ -79,5 +345,142 @@ pub enum QueryMsg {
         // Vector of Symbol pair to query
         // e.g. <BTC/USD ETH/USD, BAND/BTC> ≡ <("BTC", "USD"), ("ETH", "USD"), ("BAND", "BTC")>
         symbol_pairs: Vec<(String, String)>,
+        // Maximum age in seconds of the data of every asset relative to the current block time
+        max_age_seconds: Option<Uint64>,
+    },
+    #[returns(Config)]
+    // Returns the contract configuration
+    Config {},
+    #[returns(SymbolConfig)]
+    // Returns the per-symbol settings of a given symbol
+    GetSymbolConfig {
+        // Symbol to query
+        symbol: String,
+    },
+    #[returns(Vec < (String, String) >)]
+    // Returns the aliases and the symbols they resolve to
+    ListAliases {
+        // Only return aliases after this alias
+        start_after: Option<String>,
+        // Maximum number of aliases to return
+        limit: Option<u32>,
+    },
+    #[returns(Vec < (u16, String) >)]
+    // Returns the numeric symbol IDs and the symbols they reference
+    ListSymbolIds {
+        // Only return IDs after this ID
+        start_after: Option<u16>,
+        // Maximum number of IDs to return
+        limit: Option<u32>,
+    },
+    #[returns(Vec < (String, RefData) >)]
+    // Returns the stored symbols with their latest RefData
+    ListRefs {
+        // Only return symbols after this symbol
+        start_after: Option<String>,
+        // Maximum number of symbols to return
+        limit: Option<u32>,
+    },
+    #[returns(Vec < SymbolEntry >)]
+    // Returns the registered symbols with their metadata and latest RefData
+    ListSymbols {
+        // Only return symbols after this symbol
+        start_after: Option<String>,
+        // Maximum number of symbols to return
+        limit: Option<u32>,
+    },
+    #[returns(Vec < RefData >)]
+    // Returns the stored RefData history of a given symbol, from the newest to the oldest
+    GetRefHistory {
+        // Symbol to query
+        symbol: String,
+        // Only return entries with a resolve time older than this
+        start_after: Option<Uint64>,
+        // Maximum number of entries to return
+        limit: Option<u32>,
+    },
+    #[returns(Vec < (String, RefData) >)]
+    // Returns every symbol and RefData in the history that was saved from a given BandChain request
+    GetRefsByRequestId {
+        // Request ID on BandChain
+        request_id: Uint64,
+        // Only return symbols after this symbol
+        start_after: Option<String>,
+        // Maximum number of symbols to return
+        limit: Option<u32>,
+    },
+    #[returns(TimelockOperation)]
+    // Returns a queued timelock operation
+    GetOperation {
+        // ID of the operation
+        id: Uint64,
+    },
+    #[returns(Vec < TimelockOperation >)]
+    // Returns the queued timelock operations
+    ListOperations {
+        // Only return operations after this ID
+        start_after: Option<Uint64>,
+        // Maximum number of operations to return
+        limit: Option<u32>,
+    },
+    #[returns(Option < Multisig >)]
+    // Returns the multisig signers and threshold if multisig mode is enabled
+    Multisig {},
+    #[returns(Proposal)]
+    // Returns a multisig proposal
+    GetProposal {
+        // ID of the proposal
+        proposal_id: Uint64,
+    },
+    #[returns(Vec < Proposal >)]
+    // Returns the multisig proposals
+    ListProposals {
+        // Only return proposals after this ID
+        start_after: Option<Uint64>,
+        // Maximum number of proposals to return
+        limit: Option<u32>,
+    },
+    #[returns(Vec < (Addr, Vote) >)]
+    // Returns the votes on a multisig proposal
+    ListVotes {
+        // ID of the proposal
+        proposal_id: Uint64,
+        // Only return votes after this signer
+        start_after: Option<String>,
+        // Maximum number of votes to return
+        limit: Option<u32>,
+    },
+    #[returns(Vec < AuditEntry >)]
+    // Returns the log of administrative actions, from the oldest to the newest
+    GetAuditLog {
+        // Only return entries after this sequence number
+        start_after: Option<Uint64>,
+        // Maximum number of entries to return
+        limit: Option<u32>,
+    },
+    #[returns(Vec < PendingRound >)]
+    // Returns the relay rounds waiting for the relay threshold to be reached
+    ListPendingRounds {
+        // Only return rounds after this (request_id, resolve_time)
+        start_after: Option<(Uint64, Uint64)>,
+        // Maximum number of rounds to return
+        limit: Option<u32>,
+    },
+    #[returns(Vec < (Addr, Vec < (String, Uint64) >) >)]
+    // Returns the relayers that have submitted to a pending round and their symbol rates
+    GetRoundSubmissions {
+        // Request ID of the round
+        request_id: Uint64,
+        // Resolve time of the round
+        resolve_time: Uint64,
+    },
+    #[returns(ReferenceData)]
+    // Returns the time-weighted average ReferenceData of a given asset pairing over a window
+    // ending at the current block time, computed from the stored RefData history
+    GetTwap {
+        // Symbol pair to query e.g. BTC/USD ≡ ("BTC", "USD")
+        symbol_pair: (String, String),
+        // Length of the averaging window in seconds
+        window_seconds: Uint64,
     },
 }
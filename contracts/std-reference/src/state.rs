@@ -1,17 +1,256 @@
+use std::fmt;
+
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Uint256, Uint64};
 use cw_controllers::Admin;
-use cw_storage_plus::Map;
+use cw_storage_plus::{Item, Map};
+
+use crate::msg::ExecuteMsg;
 
 // Administrator account
 pub const ADMIN: Admin = Admin::new("admin");
 
+// Used to store the proposed administrator until the proposal is accepted or cancelled
+pub const PENDING_ADMIN: Item<PendingAdmin> = Item::new("pending_admin");
+
 // Used to store addresses of relayers and their state
-pub const RELAYERS: Map<&Addr, bool> = Map::new("relayers");
+pub const RELAYERS: Map<&Addr, RelayerInfo> = Map::new("relayers");
+
+// Used to store the symbols each relayer may write, relayers without an entry may write any symbol
+pub const RELAYER_PERMISSIONS: Map<&Addr, RelayerPermissions> = Map::new("relayer_permissions");
+
+// Used to store relayer key rotations waiting for the new address to confirm keyed by the current address
+pub const PENDING_ROTATIONS: Map<&Addr, Addr> = Map::new("pending_rotations");
+
+// Used to store the addresses granted each role keyed by (role, address)
+pub const ROLES: Map<(&str, &Addr), bool> = Map::new("roles");
 
 // Used to store RefData
 pub const REFDATA: Map<&str, RefData> = Map::new("refdata");
 
+// Used to store whether relaying is paused for every symbol
+pub const PAUSED: Item<bool> = Item::new("paused");
+
+// Used to store the symbols for which relaying is paused
+pub const PAUSED_SYMBOLS: Map<&str, bool> = Map::new("paused_symbols");
+
+// Used to store the contract configuration
+pub const CONFIG: Item<Config> = Item::new("config");
+
+// Used to store past RefData of each symbol keyed by (symbol, resolve_time)
+pub const REF_HISTORY: Map<(&str, u64), RefData> = Map::new("ref_history");
+
+// Used to store the number of history entries currently kept for each symbol
+pub const REF_HISTORY_COUNT: Map<&str, u32> = Map::new("ref_history_count");
+
+// Used to index the RefData kept in the history by BandChain request keyed by (request_id, symbol)
+pub const REQUEST_REFS: Map<(u64, &str), RefData> = Map::new("request_refs");
+
+// Used to store aliases and the symbols they resolve to when queried
+pub const ALIASES: Map<&str, String> = Map::new("aliases");
+
+// Used to store the registered symbols and their metadata
+pub const SYMBOLS: Map<&str, SymbolInfo> = Map::new("symbols");
+
+// Used to store the symbols referenced by numeric IDs in RelayCompact
+pub const SYMBOL_IDS: Map<u16, String> = Map::new("symbol_ids");
+
+// Used to store the per-symbol settings overriding the contract configuration
+pub const SYMBOL_CONFIGS: Map<&str, SymbolConfig> = Map::new("symbol_configs");
+
+// Used to store the relay rounds waiting for a quorum keyed by (request_id, resolve_time)
+pub const PENDING_ROUNDS: Map<(u64, u64), PendingRound> = Map::new("pending_rounds");
+
+// Used to store the symbol rates submitted to a pending round keyed by (request_id, resolve_time, relayer)
+pub const ROUND_SUBMISSIONS: Map<(u64, u64, &Addr), SymbolRates> = Map::new("round_submissions");
+
+// Used to store the admin operations queued by the timelock keyed by operation ID
+pub const TIMELOCK_OPERATIONS: Map<u64, TimelockOperation> = Map::new("timelock_operations");
+
+// Used to store the ID of the next queued timelock operation
+pub const NEXT_OPERATION_ID: Item<u64> = Item::new("next_operation_id");
+
+// Used to store the weighted signers holding admin authority in multisig mode
+pub const MULTISIG: Item<Multisig> = Item::new("multisig");
+
+// Used to store multisig proposals keyed by proposal ID
+pub const PROPOSALS: Map<u64, Proposal> = Map::new("proposals");
+
+// Used to store the ID of the next multisig proposal
+pub const NEXT_PROPOSAL_ID: Item<u64> = Item::new("next_proposal_id");
+
+// Used to store the votes on multisig proposals keyed by (proposal_id, signer)
+pub const VOTES: Map<(u64, &Addr), Vote> = Map::new("votes");
+
+// Used to store the append-only log of administrative actions keyed by sequence number
+pub const AUDIT_LOG: Map<u64, AuditEntry> = Map::new("audit_log");
+
+// Used to store the sequence number of the next audit log entry
+pub const NEXT_AUDIT_SEQ: Item<u64> = Item::new("next_audit_seq");
+
+// A vector of symbols and their corresponding rates
+pub type SymbolRates = Vec<(String, Uint64)>;
+
+#[cw_serde]
+pub struct Config {
+    // Number of RefData entries kept per symbol in the history, 0 disables the history
+    pub history_depth: u32,
+    // Default maximum rate change in basis points accepted by Relay, 0 disables the check
+    pub max_deviation_bps: u32,
+    // Number of distinct relayers that must submit a round before it is applied,
+    // 0 or 1 applies every Relay immediately
+    pub relay_threshold: u32,
+    // Whether Relay skips symbols that are not in the symbol registry
+    pub require_registered_symbols: bool,
+    // Symbol that all rates are quoted against, which always has a rate of 1
+    pub anchor: String,
+    // Maximum number of symbols accepted in a single Relay or ForceRelay, 0 disables the check
+    pub max_symbols_per_relay: u32,
+    // Maximum number of seconds a resolve time may be ahead of the block time, 0 disables the check
+    pub max_future_drift_seconds: Uint64,
+    // Maximum number of seconds a resolve time may be behind the block time, 0 disables the check
+    pub max_resolve_age_seconds: Uint64,
+    // Whether Relay rejects request IDs of RefData still kept in the history, which requires a
+    // non-zero history depth
+    pub reject_reused_request_ids: bool,
+    // Default minimum number of seconds between the resolve times of accepted updates of a symbol,
+    // 0 disables the check
+    pub min_update_interval_seconds: Uint64,
+    // Number of seconds sensitive admin operations are queued before they can be executed,
+    // 0 disables the timelock
+    pub timelock_delay_seconds: Uint64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            history_depth: 0,
+            max_deviation_bps: 0,
+            relay_threshold: 0,
+            require_registered_symbols: false,
+            anchor: String::from("USD"),
+            max_symbols_per_relay: 0,
+            max_future_drift_seconds: Uint64::zero(),
+            max_resolve_age_seconds: Uint64::zero(),
+            reject_reused_request_ids: false,
+            min_update_interval_seconds: Uint64::zero(),
+            timelock_delay_seconds: Uint64::zero(),
+        }
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct SymbolConfig {
+    // Maximum rate change in basis points accepted by Relay, overrides the default if set
+    pub max_deviation_bps: Option<u32>,
+    // Minimum number of seconds between accepted updates, overrides the default if set
+    pub min_update_interval_seconds: Option<Uint64>,
+}
+
+#[cw_serde]
+#[derive(Copy)]
+pub enum Role {
+    // Can add and remove relayers
+    RelayerManager,
+    // Can relay without the resolve time guard through ForceRelay
+    ForceRelayer,
+    // Can pause and unpause relaying
+    Pauser,
+    // Can update the contract configuration and per-symbol settings
+    ConfigManager,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::RelayerManager => "relayer_manager",
+            Role::ForceRelayer => "force_relayer",
+            Role::Pauser => "pauser",
+            Role::ConfigManager => "config_manager",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cw_serde]
+#[derive(Copy)]
+pub enum SkipReason {
+    // The symbol is paused
+    Paused,
+    // The symbol is registered but disabled
+    Disabled,
+    // The symbol is not registered while registration is required
+    Unregistered,
+    // The stored RefData has an equal or newer resolve time
+    Outdated,
+    // The rate moves too far from the stored rate
+    DeviationExceeded,
+    // The symbol was updated less than the minimum update interval ago
+    RateLimited,
+    // Fewer relayers than the relay threshold submitted the symbol to a finalized round
+    InsufficientQuorum,
+}
+
+impl SkipReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SkipReason::Paused => "paused",
+            SkipReason::Disabled => "disabled",
+            SkipReason::Unregistered => "unregistered",
+            SkipReason::Outdated => "outdated",
+            SkipReason::DeviationExceeded => "deviation_exceeded",
+            SkipReason::RateLimited => "rate_limited",
+            SkipReason::InsufficientQuorum => "insufficient_quorum",
+        }
+    }
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct RelaySummary {
+    // Symbols whose RefData was saved
+    pub updated: Vec<String>,
+    // Symbols that were not saved and must not be force relayed, e.g. outdated or paused symbols
+    pub skipped: Vec<(String, SkipReason)>,
+    // Symbols that were not saved because a guard rejected them, which ForceRelay can override
+    pub rejected: Vec<(String, SkipReason)>,
+    // Symbols buffered in a round that is waiting for more relayers
+    pub pending: Vec<String>,
+}
+
+impl RelaySummary {
+    pub fn extend(&mut self, other: RelaySummary) {
+        self.updated.extend(other.updated);
+        self.skipped.extend(other.skipped);
+        self.rejected.extend(other.rejected);
+        self.pending.extend(other.pending);
+    }
+
+    pub fn skip(&mut self, symbol: String, reason: SkipReason) {
+        match reason {
+            SkipReason::Paused
+            | SkipReason::Outdated
+            | SkipReason::RateLimited
+            | SkipReason::InsufficientQuorum => self.skipped.push((symbol, reason)),
+            SkipReason::Disabled | SkipReason::Unregistered | SkipReason::DeviationExceeded => {
+                self.rejected.push((symbol, reason))
+            }
+        }
+    }
+}
+
 #[cw_serde]
 pub struct RefData {
     // Rate of an asset relative to USD
@@ -51,3 +290,165 @@ impl ReferenceData {
         }
     }
 }
+
+#[cw_serde]
+pub struct RelayerInfo {
+    // Unix time of when the relayer was added
+    pub added_at: Uint64,
+    // Address that added the relayer
+    pub added_by: Addr,
+    // Optional human readable label of the relayer
+    pub label: Option<String>,
+    // Unix time of the relayer's last Relay
+    pub last_relay_time: Option<Uint64>,
+    // Number of Relays sent by the relayer
+    pub relay_count: Uint64,
+    // Request ID of the relayer's last Relay
+    pub last_request_id: Option<Uint64>,
+}
+
+impl RelayerInfo {
+    pub fn new(added_at: Uint64, added_by: Addr) -> Self {
+        RelayerInfo {
+            added_at,
+            added_by,
+            label: None,
+            last_relay_time: None,
+            relay_count: Uint64::zero(),
+            last_request_id: None,
+        }
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct RelayerPermissions {
+    // Symbols the relayer may write
+    pub symbols: Vec<String>,
+    // Categories of registered symbols the relayer may write
+    pub categories: Vec<Category>,
+}
+
+#[cw_serde]
+#[derive(Copy)]
+pub enum Category {
+    Crypto,
+    Forex,
+    Commodity,
+}
+
+#[cw_serde]
+pub struct SymbolInfo {
+    // Human readable name of the asset e.g. Bitcoin
+    pub display_name: String,
+    // Asset class of the symbol
+    pub category: Category,
+    // ID of the BandChain oracle script the rate is sourced from
+    pub oracle_script_id: Uint64,
+    // Whether Relay accepts rates for the symbol
+    pub enabled: bool,
+}
+
+#[cw_serde]
+pub struct SymbolEntry {
+    // The registered symbol
+    pub symbol: String,
+    // Metadata of the symbol
+    pub info: SymbolInfo,
+    // Latest RefData of the symbol if it has been relayed
+    pub ref_data: Option<RefData>,
+}
+
+#[cw_serde]
+pub struct PendingAdmin {
+    // Address of the proposed administrator
+    pub address: Addr,
+    // Unix time after which the proposal can no longer be accepted
+    pub expires_at: Option<Uint64>,
+}
+
+#[cw_serde]
+pub struct PendingRound {
+    // The request ID of the round
+    pub request_id: Uint64,
+    // The resolve time of the round
+    pub resolve_time: Uint64,
+    // Relayers that have submitted to the round
+    pub relayers: Vec<Addr>,
+}
+
+impl PendingRound {
+    pub fn new(request_id: Uint64, resolve_time: Uint64) -> Self {
+        PendingRound {
+            request_id,
+            resolve_time,
+            relayers: vec![],
+        }
+    }
+}
+
+#[cw_serde]
+pub struct TimelockOperation {
+    // ID of the operation
+    pub id: Uint64,
+    // Message executed on behalf of the admin once the operation is ready
+    pub msg: ExecuteMsg,
+    // Unix time from which the operation can be executed
+    pub eta: Uint64,
+    // Address that scheduled the operation
+    pub proposer: Addr,
+}
+
+#[cw_serde]
+pub struct Multisig {
+    // Signers and their voting weights
+    pub signers: Vec<(Addr, u64)>,
+    // Total weight of yes votes required to execute a proposal
+    pub threshold: u64,
+}
+
+impl Multisig {
+    pub fn weight(&self, address: &Addr) -> Option<u64> {
+        self.signers
+            .iter()
+            .find(|(signer, _)| signer == address)
+            .map(|(_, weight)| *weight)
+    }
+}
+
+#[cw_serde]
+#[derive(Copy)]
+pub enum Vote {
+    Yes,
+    No,
+}
+
+#[cw_serde]
+pub struct Proposal {
+    // ID of the proposal
+    pub id: Uint64,
+    // Message executed with the contract as admin once the proposal passes
+    pub msg: ExecuteMsg,
+    // Signer that created the proposal
+    pub proposer: Addr,
+    // Unix time after which the proposal can no longer be voted on or executed
+    pub expires_at: Option<Uint64>,
+    // Whether the proposal has been executed
+    pub executed: bool,
+}
+
+#[cw_serde]
+pub struct AuditEntry {
+    // Sequence number of the entry
+    pub seq: Uint64,
+    // Block height of the action
+    pub height: Uint64,
+    // Unix time of the action
+    pub time: Uint64,
+    // Address that performed the action
+    pub actor: Addr,
+    // Type of the action e.g. add_relayers
+    pub action: String,
+    // Parameters of the action e.g. the added relayer addresses
+    pub params: Vec<String>,
+}